@@ -2,20 +2,14 @@
 extern crate serde;
 
 use ornament::{Decorator, Text};
-use serde_json;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 enum Face {
+    #[default]
     Default,
     Strong,
 }
 
-impl Default for Face {
-    fn default() -> Self {
-        Face::Default
-    }
-}
-
 fn main() {
     let text = Decorator::with_text("This part is important.")
         .set(Face::Strong, 5..9)
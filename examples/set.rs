@@ -1,17 +1,12 @@
 use ornament::{Decorator, TextFragment};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Default)]
 enum Face {
+    #[default]
     Default,
     Strong,
 }
 
-impl Default for Face {
-    fn default() -> Self {
-        Face::Default
-    }
-}
-
 fn decorator(tf: &TextFragment<Face>) -> String {
     use Face::*;
     match tf.face {
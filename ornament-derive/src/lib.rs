@@ -0,0 +1,88 @@
+//! Derive macros for [ornament](https://docs.rs/ornament).
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, LitStr};
+
+/// Derives the rendering of a face enum from `#[face(prefix = "...", suffix = "...")]` attributes
+/// placed on its variants.
+///
+/// See the `ornament::Face` documentation for details.
+#[proc_macro_derive(Face, attributes(face))]
+pub fn derive_face(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Face can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut prefixes = Vec::new();
+    let mut suffixes = Vec::new();
+    for variant in variants {
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("face")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    prefix = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("suffix") {
+                    suffix = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `prefix` or `suffix`"))
+                }
+            })?;
+        }
+        let ident = &variant.ident;
+        prefixes.push(quote!(Self::#ident { .. } => #prefix));
+        suffixes.push(quote!(Self::#ident { .. } => #suffix));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the text rendered before a fragment with this face.
+            pub fn prefix(&self) -> &'static str {
+                match self {
+                    #(#prefixes,)*
+                }
+            }
+
+            /// Returns the text rendered after a fragment with this face.
+            pub fn suffix(&self) -> &'static str {
+                match self {
+                    #(#suffixes,)*
+                }
+            }
+
+            /// Renders a fragment wrapped in the prefix and suffix of its face.
+            pub fn render(tf: &::ornament::TextFragment<Self>) -> ::std::string::String {
+                ::std::format!("{}{}{}", tf.face.prefix(), tf.text, tf.face.suffix())
+            }
+        }
+
+        impl #impl_generics ::ornament::FaceWrap for #name #ty_generics #where_clause {
+            fn prefix(&self) -> &str {
+                Self::prefix(self)
+            }
+
+            fn suffix(&self) -> &str {
+                Self::suffix(self)
+            }
+        }
+    })
+}
@@ -0,0 +1,144 @@
+use crate::{bounded, Buffer, Decorator};
+use std::any::Any;
+use std::ops::{Range, RangeBounds};
+use std::sync::Arc;
+
+/// A span of user data attached to the text of a `Decorator`. The data is shared by the pieces
+/// of a span split by an edit.
+pub(crate) struct Annotation {
+    range: Range<usize>,
+    data: Arc<dyn Any + Send + Sync>,
+}
+
+impl Annotation {
+    /// Moves the span `offset` bytes forward.
+    pub(crate) fn shift(&mut self, offset: usize) {
+        self.range = self.range.start + offset..self.range.end + offset;
+    }
+
+    /// Moves the span after `edit` is replaced by `inserted` bytes and pushes what remains of it
+    /// to `adjusted`: nothing if the span is entirely removed, two pieces if the replaced text
+    /// was inside it.
+    pub(crate) fn adjust(
+        mut self,
+        edit: &Range<usize>,
+        inserted: usize,
+        adjusted: &mut Vec<Annotation>,
+    ) {
+        let Range { start, end } = self.range;
+        let shift = |offset: usize| offset - edit.len() + inserted;
+        if end <= edit.start {
+            adjusted.push(self);
+        } else if start >= edit.end {
+            self.range = shift(start)..shift(end);
+            adjusted.push(self);
+        } else if !edit.is_empty() && inserted > 0 {
+            // The replacement text is not annotated, the parts around it are kept.
+            let tail = (end > edit.end).then(|| Annotation {
+                range: edit.start + inserted..shift(end),
+                data: Arc::clone(&self.data),
+            });
+            if start < edit.start {
+                self.range = start..edit.start;
+                adjusted.push(self);
+            }
+            adjusted.extend(tail);
+        } else {
+            let start = if start <= edit.start {
+                start
+            } else {
+                edit.start + inserted
+            };
+            let end = if end >= edit.end {
+                shift(end)
+            } else {
+                edit.start
+            };
+            if start < end {
+                self.range = start..end;
+                adjusted.push(self);
+            }
+        }
+    }
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Attaches `data` to the given range. Unlike faces, annotations of any type can overlap and
+    /// are kept apart from the decorations: they describe what the text is (a reference, a fold
+    /// region...) rather than how it looks.
+    ///
+    /// Annotations follow the edits of the text: they are shifted by edits before them, extended
+    /// by insertions inside them, shrunk by removals and dropped when their whole range is
+    /// removed. A replacement inside an annotation splits it in two pieces sharing the same data,
+    /// the replacement text is not annotated. Text inserted at their edges is not annotated
+    /// either. Empty ranges are ignored.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Reference(&'static str);
+    ///
+    /// let mut decorator = Decorator::<()>::with_text("see `Text` and `Decorator`");
+    /// decorator
+    ///     .annotate(5..9, Reference("struct.Text"))
+    ///     .annotate(16..25, Reference("struct.Decorator"));
+    /// assert_eq!(
+    ///     decorator.annotations_at::<Reference>(17),
+    ///     vec![(16..25, &Reference("struct.Decorator"))]
+    /// );
+    /// assert!(decorator.annotations_at::<u32>(17).is_empty());
+    /// ```
+    pub fn annotate<R, A>(&mut self, range: R, data: A) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+        A: Any + Send + Sync,
+    {
+        if let Some(range) = self.safe_range(range) {
+            self.annotations.push(Annotation {
+                range,
+                data: Arc::new(data),
+            });
+        }
+        self
+    }
+
+    /// Returns the annotations of type `A` with their ranges, in attachment order.
+    pub fn annotations<A: Any>(&self) -> Vec<(Range<usize>, &A)> {
+        self.annotations
+            .iter()
+            .filter_map(|a| a.data.downcast_ref().map(|data| (a.range.clone(), data)))
+            .collect()
+    }
+
+    /// Returns the annotations of type `A` covering `offset` with their ranges, in attachment
+    /// order.
+    pub fn annotations_at<A: Any>(&self, offset: usize) -> Vec<(Range<usize>, &A)> {
+        self.annotations::<A>()
+            .into_iter()
+            .filter(|(range, _)| range.contains(&offset))
+            .collect()
+    }
+
+    /// Removes the annotations of type `A` overlapping the given range. Any kind of range can be
+    /// given, it is clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    pub fn remove_annotations<A, R>(&mut self, range: R) -> &mut Self
+    where
+        A: Any,
+        R: RangeBounds<usize>,
+    {
+        let range = bounded(range, self.len());
+        self.annotations.retain(|a| {
+            !(a.data.is::<A>() && a.range.start < range.end && range.start < a.range.end)
+        });
+        self
+    }
+}
@@ -0,0 +1,118 @@
+//! Rendering to ANSI escape sequences.
+//!
+//! Faces are mapped to [`Attrs`] and their colors are downgraded to the declared
+//! [`ColorSupport`] of the terminal, so that a single palette can be used everywhere.
+//!
+//! ```
+//! use ornament::color::{ColorSupport, NamedColor};
+//! use ornament::{Attrs, Decorator};
+//!
+//! let text = Decorator::with_text("warning: unused").set(true, 0..7).build();
+//! let attrs = |&warning: &bool| {
+//!     if warning {
+//!         Attrs::new().bold().fg((255, 135, 0))
+//!     } else {
+//!         Attrs::new()
+//!     }
+//! };
+//! # #[cfg(not(feature = "anstyle"))]
+//! # {
+//! assert_eq!(
+//!     text.render_ansi(ColorSupport::TrueColor, attrs),
+//!     "\x1b[1;38;2;255;135;0mwarning\x1b[0m: unused"
+//! );
+//! assert_eq!(
+//!     text.render_ansi(ColorSupport::Ansi16, attrs),
+//!     "\x1b[1;33mwarning\x1b[0m: unused"
+//! );
+//! # }
+//! assert_eq!(
+//!     text.render_ansi(ColorSupport::NoColor, attrs),
+//!     "\x1b[1mwarning\x1b[0m: unused"
+//! );
+//! ```
+//!
+//! With the `anstyle` feature, the sequences are written by `anstyle`, which emits one sequence
+//! per attribute, e.g. `\x1b[1m\x1b[33m` instead of `\x1b[1;33m`.
+//!
+//! [`Attrs`]: ../struct.Attrs.html
+//! [`ColorSupport`]: ../color/enum.ColorSupport.html
+
+#[cfg(not(feature = "anstyle"))]
+use crate::color::Color;
+use crate::color::ColorSupport;
+#[cfg(not(feature = "anstyle"))]
+use crate::AttrFlags;
+use crate::{Attrs, Text};
+use std::fmt::Write;
+
+/// The SGR reset sequence.
+pub const RESET: &str = "\x1b[0m";
+
+/// Returns the SGR escape sequence setting `attrs`, with its colors downgraded to `support`.
+/// The sequence is empty for the default attributes.
+pub fn sgr(attrs: &Attrs, support: ColorSupport) -> String {
+    encode(attrs.downgrade(support))
+}
+
+#[cfg(feature = "anstyle")]
+fn encode(attrs: Attrs) -> String {
+    ::anstyle::Style::from(attrs).render().to_string()
+}
+
+#[cfg(not(feature = "anstyle"))]
+fn encode(attrs: Attrs) -> String {
+    let mut codes = Vec::new();
+    for (flag, code) in [
+        (AttrFlags::BOLD, "1"),
+        (AttrFlags::ITALIC, "3"),
+        (AttrFlags::UNDERLINE, "4"),
+        (AttrFlags::REVERSE, "7"),
+        (AttrFlags::STRIKETHROUGH, "9"),
+    ] {
+        if attrs.flags.contains(flag) {
+            codes.push(code.to_owned());
+        }
+    }
+    if let Some(fg) = attrs.fg {
+        push_color(&mut codes, fg, 30);
+    }
+    if let Some(bg) = attrs.bg {
+        push_color(&mut codes, bg, 40);
+    }
+    if codes.is_empty() {
+        String::new()
+    } else {
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+#[cfg(not(feature = "anstyle"))]
+fn push_color(codes: &mut Vec<String>, color: Color, base: u8) {
+    match color {
+        Color::Named(named) if named.index() < 8 => codes.push((base + named.index()).to_string()),
+        Color::Named(named) => codes.push((base + 60 + named.index() - 8).to_string()),
+        Color::Ansi256(index) => codes.push(format!("{};5;{}", base + 8, index)),
+        Color::Rgb(r, g, b) => codes.push(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    }
+}
+
+impl<F> Text<F> {
+    /// Renders the decorated text with ANSI escape sequences, `attrs` giving the attributes of
+    /// each face. Colors are downgraded to the nearest ones displayable with `support`.
+    pub fn render_ansi<A>(&self, support: ColorSupport, attrs: A) -> String
+    where
+        A: Fn(&F) -> Attrs,
+    {
+        let mut rendered = String::new();
+        for tf in self {
+            let sequence = sgr(&attrs(&tf.face), support);
+            if sequence.is_empty() {
+                rendered.push_str(&tf.text);
+            } else {
+                write!(rendered, "{}{}{}", sequence, tf.text, RESET).unwrap();
+            }
+        }
+        rendered
+    }
+}
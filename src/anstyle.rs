@@ -0,0 +1,92 @@
+use crate::color::{Color, NamedColor};
+use crate::Text;
+use std::fmt::Write;
+
+impl From<Color> for ::anstyle::Color {
+    fn from(color: Color) -> ::anstyle::Color {
+        match color {
+            Color::Named(named) => ::anstyle::Ansi256Color(named.index())
+                .into_ansi()
+                .map_or(::anstyle::Color::Ansi256(named.index().into()), Into::into),
+            Color::Ansi256(index) => ::anstyle::Color::Ansi256(index.into()),
+            Color::Rgb(r, g, b) => ::anstyle::Color::Rgb((r, g, b).into()),
+        }
+    }
+}
+
+impl From<::anstyle::Color> for Color {
+    fn from(color: ::anstyle::Color) -> Color {
+        match color {
+            ::anstyle::Color::Ansi(ansi) => {
+                let index = ::anstyle::Ansi256Color::from_ansi(ansi).index();
+                // The 16 first indexes are always named colors.
+                Color::Named(NamedColor::from_index(index).unwrap())
+            }
+            ::anstyle::Color::Ansi256(ansi256) => Color::Ansi256(ansi256.index()),
+            ::anstyle::Color::Rgb(rgb) => Color::Rgb(rgb.r(), rgb.g(), rgb.b()),
+        }
+    }
+}
+
+#[cfg(feature = "attrs")]
+impl From<crate::Attrs> for ::anstyle::Style {
+    fn from(attrs: crate::Attrs) -> ::anstyle::Style {
+        use crate::AttrFlags;
+        use ::anstyle::Effects;
+
+        let mut effects = Effects::new();
+        for (flag, effect) in [
+            (AttrFlags::BOLD, Effects::BOLD),
+            (AttrFlags::ITALIC, Effects::ITALIC),
+            (AttrFlags::UNDERLINE, Effects::UNDERLINE),
+            (AttrFlags::REVERSE, Effects::INVERT),
+            (AttrFlags::STRIKETHROUGH, Effects::STRIKETHROUGH),
+        ] {
+            if attrs.flags.contains(flag) {
+                effects |= effect;
+            }
+        }
+        ::anstyle::Style::new()
+            .fg_color(attrs.fg.map(Into::into))
+            .bg_color(attrs.bg.map(Into::into))
+            .effects(effects)
+    }
+}
+
+impl<F> Text<F> {
+    /// Renders the decorated text with ANSI escape sequences, using the `anstyle` style returned
+    /// by `style` for each face. The style is reset after each styled fragment.
+    ///
+    /// ```
+    /// use anstyle::{AnsiColor, Style};
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("An important word.").set(true, 3..12).build();
+    /// let rendered = text.render_anstyle(|&strong| {
+    ///     if strong {
+    ///         Style::new().bold().fg_color(Some(AnsiColor::Red.into()))
+    ///     } else {
+    ///         Style::new()
+    ///     }
+    /// });
+    /// assert_eq!(rendered, "An \x1b[1m\x1b[31mimportant\x1b[0m word.");
+    /// ```
+    pub fn render_anstyle<S>(&self, style: S) -> String
+    where
+        S: Fn(&F) -> ::anstyle::Style,
+    {
+        let mut rendered = String::new();
+        for tf in self {
+            let style = style(&tf.face);
+            write!(
+                rendered,
+                "{}{}{}",
+                style.render(),
+                tf.text,
+                style.render_reset()
+            )
+            .unwrap();
+        }
+        rendered
+    }
+}
@@ -0,0 +1,124 @@
+use crate::color::{Color, ColorSupport};
+use crate::FaceMerge;
+
+bitflags::bitflags! {
+    /// The text attributes of an [`Attrs`] face.
+    ///
+    /// [`Attrs`]: struct.Attrs.html
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+    pub struct AttrFlags: u8 {
+        const BOLD = 1;
+        const ITALIC = 1 << 1;
+        const UNDERLINE = 1 << 2;
+        const REVERSE = 1 << 3;
+        const STRIKETHROUGH = 1 << 4;
+    }
+}
+
+/// A ready to use face made of text attributes and optional colors.
+///
+/// The default value has no attribute and no color, which stands for the terminal (or
+/// renderer) defaults. Merging two `Attrs` combines their attributes and the colors of the top
+/// face take precedence when they are set.
+///
+/// ```
+/// use ornament::color::{Color, NamedColor};
+/// use ornament::{AttrFlags, Attrs, FaceMerge};
+///
+/// let bold_red = Attrs::new().bold().fg(NamedColor::Red);
+/// let underlined_blue = Attrs::new().underline().fg(NamedColor::Blue);
+/// let merged = bold_red.merge(&underlined_blue);
+/// assert_eq!(merged.flags, AttrFlags::BOLD | AttrFlags::UNDERLINE);
+/// assert_eq!(merged.fg, Some(Color::Named(NamedColor::Blue)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Attrs {
+    /// The text attributes.
+    pub flags: AttrFlags,
+    /// The foreground color.
+    pub fg: Option<Color>,
+    /// The background color.
+    pub bg: Option<Color>,
+}
+
+impl Attrs {
+    /// Creates a new `Attrs` without attributes nor colors.
+    pub const fn new() -> Attrs {
+        Attrs {
+            flags: AttrFlags::empty(),
+            fg: None,
+            bg: None,
+        }
+    }
+
+    /// Adds the given attributes.
+    pub const fn with(self, flags: AttrFlags) -> Attrs {
+        Attrs {
+            flags: self.flags.union(flags),
+            ..self
+        }
+    }
+
+    /// Adds the bold attribute.
+    pub const fn bold(self) -> Attrs {
+        self.with(AttrFlags::BOLD)
+    }
+
+    /// Adds the italic attribute.
+    pub const fn italic(self) -> Attrs {
+        self.with(AttrFlags::ITALIC)
+    }
+
+    /// Adds the underline attribute.
+    pub const fn underline(self) -> Attrs {
+        self.with(AttrFlags::UNDERLINE)
+    }
+
+    /// Adds the reverse attribute.
+    pub const fn reverse(self) -> Attrs {
+        self.with(AttrFlags::REVERSE)
+    }
+
+    /// Adds the strikethrough attribute.
+    pub const fn strikethrough(self) -> Attrs {
+        self.with(AttrFlags::STRIKETHROUGH)
+    }
+
+    /// Sets the foreground color.
+    pub fn fg<C: Into<Color>>(self, color: C) -> Attrs {
+        Attrs {
+            fg: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the background color.
+    pub fn bg<C: Into<Color>>(self, color: C) -> Attrs {
+        Attrs {
+            bg: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Downgrades the colors to the nearest ones displayable with the given support, removing
+    /// them if colors are not supported.
+    pub fn downgrade(self, support: ColorSupport) -> Attrs {
+        Attrs {
+            fg: self.fg.and_then(|c| c.downgrade(support)),
+            bg: self.bg.and_then(|c| c.downgrade(support)),
+            ..self
+        }
+    }
+}
+
+impl FaceMerge for Attrs {
+    fn merge(&self, other: &Self) -> Self {
+        Attrs {
+            flags: self.flags | other.flags,
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+        }
+    }
+}
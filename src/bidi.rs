@@ -0,0 +1,69 @@
+use crate::{Decorator, Text};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Reorders the decorated text from logical order to visual order, following the Unicode
+    /// bidirectional algorithm. Fragments are split and reversed as needed, keeping their faces.
+    ///
+    /// `direction` is the base direction of the paragraphs, with `None` it is guessed from the
+    /// first strong character of each paragraph. This is meant for terminals which don't reorder
+    /// text themselves. Each line is reordered on its own, line breaks are kept in place.
+    /// Right-to-left runs are reversed by character, the nonspacing marks (e.g. Hebrew points or
+    /// Arabic harakat) staying after their base character.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use unicode_bidi::Level;
+    ///
+    /// let text = Decorator::with_text("abc אבג def").set(1, 4..10).build();
+    /// let visual = text.to_visual_order(Some(Level::ltr()));
+    /// assert_eq!(visual.plain(), "abc גבא def");
+    /// assert_eq!(visual.face_at(4), Some(&1));
+    /// ```
+    pub fn to_visual_order(&self, direction: Option<Level>) -> Text<F> {
+        let plain = self.plain();
+        let info = BidiInfo::new(&plain, direction);
+        let mut decorator = Decorator::new();
+        for paragraph in &info.paragraphs {
+            let range = paragraph.range.clone();
+            let content = plain[range.clone()].trim_end_matches(['\n', '\r', '\u{2029}']);
+            let line = range.start..range.start + content.len();
+            if !line.is_empty() {
+                let (levels, runs) = info.visual_runs(paragraph, line.clone());
+                for run in runs {
+                    let pieces = self.slice(run.clone());
+                    if levels[run.start].is_rtl() {
+                        append_reversed(&mut decorator, &pieces);
+                    } else {
+                        decorator.append_text(&pieces);
+                    }
+                }
+            }
+            decorator.append_text(&self.slice(line.end..range.end));
+        }
+        decorator.build()
+    }
+}
+
+/// Appends the characters of `pieces` in reverse order, keeping the nonspacing marks after the
+/// character they apply to.
+fn append_reversed<F>(decorator: &mut Decorator<F>, pieces: &Text<F>)
+where
+    F: Clone + Default + PartialEq,
+{
+    let mut clusters: Vec<Vec<(char, &F)>> = Vec::new();
+    for tf in pieces {
+        for c in tf.text.chars() {
+            match clusters.last_mut() {
+                Some(cluster) if bidi_class(c) == BidiClass::NSM => cluster.push((c, &tf.face)),
+                _ => clusters.push(vec![(c, &tf.face)]),
+            }
+        }
+    }
+    let mut buf = [0; 4];
+    for (c, face) in clusters.into_iter().rev().flatten() {
+        decorator
+            .set_face(face.clone())
+            .append(c.encode_utf8(&mut buf));
+    }
+}
@@ -0,0 +1,86 @@
+//! Reads a decorated text in the compact encoding (see `Text::to_compact_string`) from stdin and
+//! prints it with ANSI escape sequences.
+//!
+//! Faces are `+` separated styles: `bold`, `italic`, `underline`, `reverse`, `strikethrough`, a
+//! color name such as `red` or `bright_blue` for the foreground, and `on_` followed by a color
+//! name for the background.
+//!
+//! ```text
+//! $ printf 'warning: unused‹bold+yellow:0..7›' | ornament-ansi
+//! ```
+
+use ornament::color::{ColorSupport, NamedColor};
+use ornament::{Attrs, Text};
+use std::io::{self, Read};
+use std::process;
+
+const COLORS: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+fn color(name: &str) -> Option<NamedColor> {
+    let index = COLORS.iter().position(|&color| color == name)?;
+    NamedColor::from_index(index as u8)
+}
+
+fn attrs(face: &str) -> Result<Attrs, String> {
+    let mut attrs = Attrs::new();
+    for style in face.split('+').filter(|style| !style.is_empty()) {
+        attrs = match style {
+            "bold" => attrs.bold(),
+            "italic" => attrs.italic(),
+            "underline" => attrs.underline(),
+            "reverse" => attrs.reverse(),
+            "strikethrough" => attrs.strikethrough(),
+            _ => match style.strip_prefix("on_") {
+                Some(name) => color(name).map(|c| attrs.bg(c)),
+                None => color(style).map(|c| attrs.fg(c)),
+            }
+            .ok_or_else(|| format!("unknown style: {}", style))?,
+        };
+    }
+    Ok(attrs)
+}
+
+fn main() {
+    let mut input = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut input) {
+        eprintln!("ornament-ansi: {}", err);
+        process::exit(1);
+    }
+    let input = input.strip_suffix('\n').unwrap_or(&input);
+
+    let text: Text<String> = match Text::from_compact_str(input) {
+        Some(text) => text,
+        None => {
+            eprintln!("ornament-ansi: malformed input");
+            process::exit(1);
+        }
+    };
+    for tf in &text {
+        if let Err(err) = attrs(&tf.face) {
+            eprintln!("ornament-ansi: {}", err);
+            process::exit(1);
+        }
+    }
+    let support = ColorSupport::from_env();
+    println!(
+        "{}",
+        text.render_ansi(support, |face| attrs(face).unwrap_or_default())
+    );
+}
@@ -0,0 +1,138 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// The text storage used by a [`Decorator`].
+///
+/// All offsets are expressed in bytes, like everywhere else in this crate. `String` is the default
+/// storage, other implementations can be provided for specific workloads (e.g. [`Rope`] behind
+/// the `ropey` feature for large documents).
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`Rope`]: https://docs.rs/ropey/1/ropey/struct.Rope.html
+pub trait Buffer: Default {
+    /// Returns the length of the stored text, in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the buffer contains no text.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `text` at the end of the buffer.
+    fn push_str(&mut self, text: &str);
+
+    /// Replaces the text in the given byte range by `text`.
+    ///
+    /// The default implementation rebuilds the whole buffer, storages supporting in place edits
+    /// should override it.
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        let len = self.len();
+        let mut new = Self::default();
+        new.push_str(&self.slice(0..range.start));
+        new.push_str(text);
+        new.push_str(&self.slice(range.end..len));
+        *self = new;
+    }
+
+    /// Replaces each of the sorted and non-overlapping byte ranges of `edits` by its text. Ranges
+    /// are given relatively to the text before the edits.
+    ///
+    /// The default implementation rebuilds the whole buffer in a single pass, storages whose
+    /// [`replace_range`] is cheap should override it to edit in place.
+    ///
+    /// [`replace_range`]: trait.Buffer.html#method.replace_range
+    fn replace_ranges(&mut self, edits: &[(Range<usize>, &str)]) {
+        let len = self.len();
+        let mut new = Self::default();
+        let mut pos = 0;
+        for (range, text) in edits {
+            new.push_str(&self.slice(pos..range.start));
+            new.push_str(text);
+            pos = range.end;
+        }
+        new.push_str(&self.slice(pos..len));
+        *self = new;
+    }
+
+    /// Removes all the text, keeping the allocated capacity where possible.
+    fn clear(&mut self) {
+        let len = self.len();
+        self.replace_range(0..len, "");
+    }
+
+    /// Returns the text in the given byte range.
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str>;
+
+    /// Returns the number of bytes allocated by the buffer.
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    /// Shrinks the capacity of the buffer as much as possible.
+    fn shrink_to_fit(&mut self) {}
+}
+
+impl Buffer for String {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.push_str(text);
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        self.replace_range(range, text);
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.shrink_to_fit();
+    }
+}
+
+#[cfg(feature = "ropey")]
+impl Buffer for ropey::Rope {
+    fn len(&self) -> usize {
+        self.len_bytes()
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.insert(self.len_chars(), text);
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        let start = self.byte_to_char(range.start);
+        self.remove(start..self.byte_to_char(range.end));
+        self.insert(start, text);
+    }
+
+    fn replace_ranges(&mut self, edits: &[(Range<usize>, &str)]) {
+        for (range, text) in edits.iter().rev() {
+            Buffer::replace_range(self, range.clone(), text);
+        }
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        self.byte_slice(range).into()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.shrink_to_fit();
+    }
+}
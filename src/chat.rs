@@ -0,0 +1,321 @@
+//! Renderers for the markdown dialects and message formats of chat platforms.
+//!
+//! Faces are mapped to a [`ChatStyle`] and rendered with the emphasis syntax of a [`Dialect`].
+//! The markup characters found in the text are escaped so that they are displayed as is.
+//!
+//! ```
+//! use ornament::chat::{ChatStyle, Dialect};
+//! use ornament::Decorator;
+//!
+//! let text = Decorator::with_text("Build *failed* on main")
+//!     .set("bold", 0..14)
+//!     .set("code", 18..22)
+//!     .build();
+//! let style = |face: &&str| match *face {
+//!     "bold" => ChatStyle::new().bold(),
+//!     "code" => ChatStyle::new().code(),
+//!     _ => ChatStyle::new(),
+//! };
+//! assert_eq!(
+//!     text.render_chat(Dialect::Discord, style),
+//!     "**Build \\*failed\\*** on `main`"
+//! );
+//! assert_eq!(
+//!     text.render_chat(Dialect::Slack, style),
+//!     "*Build \u{200b}*failed\u{200b}** on `main`"
+//! );
+//! ```
+//!
+//! [`ChatStyle`]: struct.ChatStyle.html
+//! [`Dialect`]: enum.Dialect.html
+
+use crate::Text;
+
+/// A chat markdown dialect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Dialect {
+    /// Slack `mrkdwn`: `*bold*`, `_italic_`, `~strike~` and `` `code` ``.
+    ///
+    /// Slack has no escape character: `&`, `<` and `>` are replaced by their entities and a
+    /// zero-width space is inserted before the other markup characters to break them.
+    Slack,
+    /// Discord markdown: `**bold**`, `*italic*`, `~~strike~~` and `` `code` ``.
+    ///
+    /// Markup characters are escaped with a backslash.
+    Discord,
+}
+
+/// The emphasis of a piece of chat text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct ChatStyle {
+    /// Bold emphasis.
+    pub bold: bool,
+    /// Italic emphasis.
+    pub italic: bool,
+    /// Strikethrough.
+    pub strike: bool,
+    /// Inline code, its content is never escaped nor emphasized. Code which can't be delimited
+    /// in the dialect (backticks for Slack, double backticks for Discord) is rendered as escaped
+    /// text instead.
+    pub code: bool,
+}
+
+impl ChatStyle {
+    /// Creates a new `ChatStyle` without emphasis.
+    pub const fn new() -> ChatStyle {
+        ChatStyle {
+            bold: false,
+            italic: false,
+            strike: false,
+            code: false,
+        }
+    }
+
+    /// Adds bold emphasis.
+    pub const fn bold(self) -> ChatStyle {
+        ChatStyle { bold: true, ..self }
+    }
+
+    /// Adds italic emphasis.
+    pub const fn italic(self) -> ChatStyle {
+        ChatStyle {
+            italic: true,
+            ..self
+        }
+    }
+
+    /// Adds strikethrough.
+    pub const fn strike(self) -> ChatStyle {
+        ChatStyle {
+            strike: true,
+            ..self
+        }
+    }
+
+    /// Makes it inline code.
+    pub const fn code(self) -> ChatStyle {
+        ChatStyle { code: true, ..self }
+    }
+}
+
+impl Dialect {
+    fn markers(self, style: ChatStyle) -> Vec<&'static str> {
+        let (bold, italic, strike) = match self {
+            Dialect::Slack => ("*", "_", "~"),
+            Dialect::Discord => ("**", "*", "~~"),
+        };
+        let mut markers = Vec::new();
+        for (enabled, marker) in [
+            (style.bold, bold),
+            (style.italic, italic),
+            (style.strike, strike),
+            (style.code, "`"),
+        ] {
+            if enabled {
+                markers.push(marker);
+            }
+        }
+        markers
+    }
+
+    /// Escapes `text` so that it is displayed as is.
+    ///
+    /// ```
+    /// use ornament::chat::Dialect;
+    ///
+    /// assert_eq!(Dialect::Discord.escape("a_b*c"), "a\\_b\\*c");
+    /// assert_eq!(Dialect::Slack.escape("a<b"), "a&lt;b");
+    /// ```
+    pub fn escape(self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match (self, c) {
+                (Dialect::Slack, '&') => escaped.push_str("&amp;"),
+                (Dialect::Slack, '<') => escaped.push_str("&lt;"),
+                (Dialect::Slack, '>') => escaped.push_str("&gt;"),
+                (Dialect::Slack, '*' | '_' | '~' | '`') => {
+                    escaped.push('\u{200b}');
+                    escaped.push(c);
+                }
+                (Dialect::Discord, '\\' | '*' | '_' | '~' | '`' | '|') => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Returns `text` as inline code, or `None` if its backticks can't be delimited: Slack has
+    /// no way to put a backtick in inline code and Discord only has single and double backtick
+    /// delimiters.
+    fn code(self, text: &str) -> Option<String> {
+        match self {
+            Dialect::Slack if text.contains('`') => None,
+            Dialect::Slack => Some(format!(
+                "`{}`",
+                text.replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+            )),
+            Dialect::Discord if text.contains("``") => None,
+            Dialect::Discord if text.contains('`') => Some(format!("`` {} ``", text)),
+            Dialect::Discord => Some(format!("`{}`", text)),
+        }
+    }
+
+    fn push_styled(self, rendered: &mut String, text: &str, style: ChatStyle) {
+        let core = text.trim();
+        let code = if style.code { self.code(core) } else { None };
+        let style = ChatStyle {
+            code: code.is_some(),
+            ..style
+        };
+        let markers = self.markers(style);
+        if core.is_empty() || markers.is_empty() {
+            rendered.push_str(&self.escape(text));
+            return;
+        }
+
+        // Markers surrounded by whitespace are not recognized, keep it outside.
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+        rendered.push_str(leading);
+        let content = code.unwrap_or_else(|| self.escape(core));
+        let emphasis = &markers[..markers.len() - style.code as usize];
+        for marker in emphasis {
+            rendered.push_str(marker);
+        }
+        rendered.push_str(&content);
+        for marker in emphasis.iter().rev() {
+            rendered.push_str(marker);
+        }
+        rendered.push_str(trailing);
+    }
+}
+
+impl<F> Text<F> {
+    /// Renders the decorated text in the given chat `dialect`, `style` giving the emphasis of
+    /// each face.
+    ///
+    /// Consecutive fragments with the same style are rendered together and the whitespace at the
+    /// edges of an emphasized piece is kept outside of its markers.
+    pub fn render_chat<S>(&self, dialect: Dialect, style: S) -> String
+    where
+        S: Fn(&F) -> ChatStyle,
+    {
+        let mut rendered = String::new();
+        let mut pending = String::new();
+        let mut current = ChatStyle::new();
+        for tf in self {
+            let next = style(&tf.face);
+            if next != current {
+                dialect.push_styled(&mut rendered, &pending, current);
+                pending.clear();
+                current = next;
+            }
+            pending.push_str(&tf.text);
+        }
+        dialect.push_styled(&mut rendered, &pending, current);
+        rendered
+    }
+}
+
+/// The type of a Telegram message entity.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum EntityKind {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Spoiler,
+    Blockquote,
+    Code,
+    Pre {
+        #[cfg_attr(
+            feature = "serde_support",
+            serde(skip_serializing_if = "Option::is_none")
+        )]
+        language: Option<String>,
+    },
+    TextLink {
+        url: String,
+    },
+}
+
+/// A Telegram Bot API `MessageEntity`.
+///
+/// The offset and the length are counted in UTF-16 code units, as expected by the API.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct MessageEntity {
+    #[cfg_attr(feature = "serde_support", serde(flatten))]
+    pub kind: EntityKind,
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl<F> Text<F> {
+    /// Converts the decorated text into its plain text and the Telegram entities decorating it,
+    /// `entities` returning the entity types of a face.
+    ///
+    /// An entity spanning consecutive fragments is merged into a single one.
+    ///
+    /// ```
+    /// use ornament::chat::{EntityKind, MessageEntity};
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("Rust 🦀 is fun").set(true, 13..16).build();
+    /// let (plain, entities) = text.to_telegram(|&bold| {
+    ///     if bold {
+    ///         vec![EntityKind::Bold]
+    ///     } else {
+    ///         Vec::new()
+    ///     }
+    /// });
+    /// assert_eq!(plain, "Rust 🦀 is fun");
+    /// assert_eq!(
+    ///     entities,
+    ///     vec![MessageEntity {
+    ///         kind: EntityKind::Bold,
+    ///         offset: 11,
+    ///         length: 3,
+    ///     }]
+    /// );
+    /// ```
+    pub fn to_telegram<E>(&self, entities: E) -> (String, Vec<MessageEntity>)
+    where
+        E: Fn(&F) -> Vec<EntityKind>,
+    {
+        let mut result: Vec<MessageEntity> = Vec::new();
+        let mut offset = 0;
+        for tf in self {
+            let length = tf.text.encode_utf16().count();
+            for kind in entities(&tf.face) {
+                match result
+                    .iter_mut()
+                    .rev()
+                    .filter(|e| e.offset + e.length == offset)
+                    .find(|e| e.kind == kind)
+                {
+                    Some(entity) => entity.length += length,
+                    None => result.push(MessageEntity {
+                        kind,
+                        offset,
+                        length,
+                    }),
+                }
+            }
+            offset += length;
+        }
+        (self.plain(), result)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::decorations::Decorations;
+use crate::{Buffer, Decorator};
+use std::sync::{Arc, Mutex};
+
+/// The decorations of a text, as saved for a checkpoint.
+struct Snapshot<F> {
+    decorations: Decorations<F>,
+    priorities: Option<Decorations<u32>>,
+}
+
+/// The part of a checkpoint shared with the decorator.
+pub(crate) struct Pending<F> {
+    len: usize,
+    revision: usize,
+    snapshot: Option<Arc<Snapshot<F>>>,
+}
+
+/// A saved state of a [`Decorator`], created by [`Decorator::checkpoint`] and restored by
+/// [`Decorator::revert_to`].
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`Decorator::checkpoint`]: struct.Decorator.html#method.checkpoint
+/// [`Decorator::revert_to`]: struct.Decorator.html#method.revert_to
+pub struct Checkpoint<F> {
+    current_face: F,
+    face_stack: Vec<F>,
+    pending: Arc<Mutex<Pending<F>>>,
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Saves the current state of the decorator: the length of the text, the faces assigned to
+    /// it and the current face. The state is restored by [`revert_to`].
+    ///
+    /// Taking a checkpoint is cheap: the decorations are only saved when faces are assigned to the
+    /// text appended before the checkpoint.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut builder = Decorator::with_text("Warning:");
+    /// for word in ["disk", "almost", "full"] {
+    ///     let checkpoint = builder.checkpoint();
+    ///     builder.reset_face().append(" ").set_face("word").append(word);
+    ///     if builder.len() > 16 {
+    ///         builder.revert_to(checkpoint).append("…");
+    ///         break;
+    ///     }
+    /// }
+    /// let text = builder.build();
+    /// assert_eq!(text.plain(), "Warning: disk…");
+    /// assert_eq!(text.face_at(9), Some(&"word"));
+    /// assert_eq!(text.face_at(13), Some(&"word"));
+    /// ```
+    ///
+    /// [`revert_to`]: struct.Decorator.html#method.revert_to
+    pub fn checkpoint(&mut self) -> Checkpoint<F> {
+        // Forget the checkpoints which have been dropped.
+        self.checkpoints
+            .retain(|pending| Arc::strong_count(pending) > 1);
+        let pending = Arc::new(Mutex::new(Pending {
+            len: self.text.len(),
+            revision: self.revision,
+            snapshot: None,
+        }));
+        self.checkpoints.push(Arc::clone(&pending));
+        Checkpoint {
+            current_face: self.current_face.clone(),
+            face_stack: self.face_stack.clone(),
+            pending,
+        }
+    }
+
+    /// Restores the state saved by [`checkpoint`]: the text appended since is removed, the faces
+    /// assigned since are discarded and the current face is restored. Cursors and annotations
+    /// are adjusted as for a removal of the appended text.
+    ///
+    /// The checkpoints taken after this one can no longer be reverted to.
+    ///
+    /// This method is chainable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the text was edited other than by appending since the checkpoint, e.g. with
+    /// [`apply_edits`], [`clear`] or by reverting to an earlier checkpoint, or if the checkpoint
+    /// was taken by another decorator.
+    ///
+    /// [`checkpoint`]: struct.Decorator.html#method.checkpoint
+    /// [`apply_edits`]: struct.Decorator.html#method.apply_edits
+    /// [`clear`]: struct.Decorator.html#method.clear
+    pub fn revert_to(&mut self, checkpoint: Checkpoint<F>) -> &mut Self {
+        let (len, snapshot) = {
+            let pending = checkpoint.pending.lock().expect("poisoned checkpoint");
+            assert!(
+                pending.revision == self.revision && pending.len <= self.text.len(),
+                "text edited since the checkpoint"
+            );
+            (pending.len, pending.snapshot.clone())
+        };
+
+        let idx = self
+            .checkpoints
+            .iter()
+            .position(|pending| Arc::ptr_eq(pending, &checkpoint.pending))
+            .expect("checkpoint taken by another decorator");
+
+        // The checkpoints taken before this one stay valid, the other ones become stale.
+        self.revision += 1;
+        self.checkpoints.truncate(idx);
+        for pending in &self.checkpoints {
+            if let Ok(mut pending) = pending.lock() {
+                if pending.revision + 1 == self.revision {
+                    pending.revision = self.revision;
+                }
+            }
+        }
+
+        let range = len..self.text.len();
+        self.text.replace_range(range.clone(), "");
+        self.edited(range, 0);
+        match snapshot {
+            Some(snapshot) => {
+                self.invalidate(0);
+                self.decorations = snapshot.decorations.clone();
+                self.priorities = snapshot.priorities.clone();
+            }
+            None => {
+                self.decorations.truncate(len);
+                if let Some(priorities) = &mut self.priorities {
+                    priorities.truncate(len);
+                }
+            }
+        }
+        self.current_face = checkpoint.current_face;
+        self.face_stack = checkpoint.face_stack;
+        self
+    }
+
+    /// Saves the decorations for the checkpoints which need them before faces are assigned from
+    /// `offset`: as long as only text is appended, a checkpoint is restored by truncating the
+    /// decorations.
+    pub(crate) fn preserve_checkpoints(&self, offset: usize) {
+        let mut last: Option<(usize, Arc<Snapshot<F>>)> = None;
+        for pending in &self.checkpoints {
+            if let Ok(mut pending) = pending.lock() {
+                if pending.snapshot.is_some()
+                    || pending.revision != self.revision
+                    || offset >= pending.len
+                {
+                    continue;
+                }
+                let snapshot = match &last {
+                    Some((len, snapshot)) if *len == pending.len => Arc::clone(snapshot),
+                    _ => {
+                        let mut decorations = self.decorations.clone();
+                        decorations.truncate(pending.len);
+                        let mut priorities = self.priorities.clone();
+                        if let Some(priorities) = &mut priorities {
+                            priorities.truncate(pending.len);
+                        }
+                        Arc::new(Snapshot {
+                            decorations,
+                            priorities,
+                        })
+                    }
+                };
+                last = Some((pending.len, Arc::clone(&snapshot)));
+                pending.snapshot = Some(snapshot);
+            }
+        }
+    }
+}
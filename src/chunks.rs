@@ -0,0 +1,105 @@
+use crate::{Text, TextFragment};
+use std::mem;
+
+impl<F: Clone> Text<F> {
+    /// Splits the decorated text into pieces of at most `max_bytes` bytes, such as messages with
+    /// a size limit.
+    ///
+    /// Pieces end at fragment boundaries when possible, fragments longer than `max_bytes` are
+    /// split at `char` boundaries. A `char` longer than `max_bytes` makes its own piece.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_bytes` is zero.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("ab cdefgh").set(1, 3..9).build();
+    /// let chunks = text.chunks(4);
+    /// assert_eq!(
+    ///     chunks.iter().map(|chunk| chunk.plain()).collect::<Vec<_>>(),
+    ///     vec!["ab ", "cdef", "gh"]
+    /// );
+    /// assert_eq!(chunks[2].face_at(0), Some(&1));
+    /// ```
+    pub fn chunks(&self, max_bytes: usize) -> Vec<Text<F>> {
+        self.chunks_rendered(max_bytes, |tf| tf.text.clone())
+    }
+
+    /// Splits the decorated text as [`chunks`] does, into pieces whose rendering with
+    /// `decorator` is at most `max_bytes` bytes long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_bytes` is zero.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("see this").set(true, 4..8).build();
+    /// let bold = |tf: &ornament::TextFragment<bool>| {
+    ///     if tf.face {
+    ///         format!("**{}**", tf.text)
+    ///     } else {
+    ///         tf.text.clone()
+    ///     }
+    /// };
+    /// let chunks = text.chunks_rendered(7, bold);
+    /// assert_eq!(
+    ///     chunks.iter().map(|chunk| chunk.render(bold)).collect::<Vec<_>>(),
+    ///     vec!["see ", "**thi**", "**s**"]
+    /// );
+    /// ```
+    ///
+    /// [`chunks`]: struct.Text.html#method.chunks
+    pub fn chunks_rendered<G>(&self, max_bytes: usize, decorator: G) -> Vec<Text<F>>
+    where
+        G: Fn(&TextFragment<F>) -> String,
+    {
+        assert!(max_bytes > 0, "chunks must be at least one byte long");
+        let mut chunks = Vec::new();
+        let mut current: Vec<TextFragment<F>> = Vec::new();
+        let mut size = 0;
+        for tf in self {
+            let mut rest = tf.clone();
+            loop {
+                let rendered = decorator(&rest).len();
+                if size + rendered <= max_bytes {
+                    size += rendered;
+                    current.push(rest);
+                    break;
+                }
+                if !current.is_empty() {
+                    chunks.push(mem::take(&mut current).into());
+                    size = 0;
+                    continue;
+                }
+
+                // The fragment doesn't fit in a piece on its own, keep its longest prefix which
+                // does, or at least its first `char`.
+                let prefix = |end: usize| TextFragment {
+                    text: rest.text[..end].to_owned(),
+                    face: rest.face.clone(),
+                };
+                let boundaries: Vec<usize> =
+                    rest.text.char_indices().skip(1).map(|(i, _)| i).collect();
+                let fitting =
+                    boundaries.partition_point(|&end| decorator(&prefix(end)).len() <= max_bytes);
+                let end = match fitting {
+                    0 => boundaries.first().copied().unwrap_or(rest.text.len()),
+                    n => boundaries[n - 1],
+                };
+                chunks.push(prefix(end).into());
+                if end == rest.text.len() {
+                    break;
+                }
+                rest.text.replace_range(..end, "");
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(current.into());
+        }
+        chunks
+    }
+}
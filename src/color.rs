@@ -0,0 +1,267 @@
+//! Color types with conversions between the different color spaces terminals support.
+
+/// One of the 16 standard terminal colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum NamedColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl NamedColor {
+    const ALL: [NamedColor; 16] = [
+        NamedColor::Black,
+        NamedColor::Red,
+        NamedColor::Green,
+        NamedColor::Yellow,
+        NamedColor::Blue,
+        NamedColor::Magenta,
+        NamedColor::Cyan,
+        NamedColor::White,
+        NamedColor::BrightBlack,
+        NamedColor::BrightRed,
+        NamedColor::BrightGreen,
+        NamedColor::BrightYellow,
+        NamedColor::BrightBlue,
+        NamedColor::BrightMagenta,
+        NamedColor::BrightCyan,
+        NamedColor::BrightWhite,
+    ];
+
+    // The xterm default values.
+    const RGB: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+
+    /// Returns the index of the color in the 256 colors palette, between 0 and 15.
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the color at `index` in the 256 colors palette, or `None` if `index` is greater
+    /// than 15.
+    pub fn from_index(index: u8) -> Option<NamedColor> {
+        NamedColor::ALL.get(usize::from(index)).copied()
+    }
+
+    /// Returns the RGB value of the color, using the xterm defaults.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        NamedColor::RGB[usize::from(self.index())]
+    }
+}
+
+// The intensity levels of the 6x6x6 color cube of the 256 colors palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn nearest_level(c: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(c)).abs())
+        .unwrap()
+}
+
+/// A terminal color.
+///
+/// A color can be converted to the other color spaces, the conversion to a more limited space
+/// picks the nearest color.
+///
+/// ```
+/// use ornament::color::{Color, NamedColor};
+///
+/// let orange = Color::Rgb(255, 135, 0);
+/// assert_eq!(orange.to_ansi256(), 208);
+/// assert_eq!(orange.to_named(), NamedColor::Yellow);
+/// assert_eq!(Color::Ansi256(208).to_rgb(), (255, 135, 0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Color {
+    /// One of the 16 standard colors.
+    Named(NamedColor),
+    /// A color of the 256 colors palette.
+    Ansi256(u8),
+    /// A true color.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Returns the RGB value of the color. Standard colors use the xterm defaults.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Named(named) => named.to_rgb(),
+            Color::Ansi256(index) => match NamedColor::from_index(index) {
+                Some(named) => named.to_rgb(),
+                None if index < 232 => {
+                    let cube = usize::from(index - 16);
+                    (
+                        CUBE_LEVELS[cube / 36],
+                        CUBE_LEVELS[cube / 6 % 6],
+                        CUBE_LEVELS[cube % 6],
+                    )
+                }
+                None => {
+                    let gray = 8 + 10 * (index - 232);
+                    (gray, gray, gray)
+                }
+            },
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// Returns the index of the nearest color in the 256 colors palette.
+    pub fn to_ansi256(self) -> u8 {
+        match self {
+            Color::Named(named) => named.index(),
+            Color::Ansi256(index) => index,
+            Color::Rgb(r, g, b) => {
+                let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+                let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+                let average = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+                let gray = 232 + (average.saturating_sub(3) / 10).min(23);
+                let rgb = (r, g, b);
+                if distance(Color::Ansi256(gray).to_rgb(), rgb)
+                    < distance(Color::Ansi256(cube).to_rgb(), rgb)
+                {
+                    gray
+                } else {
+                    cube
+                }
+            }
+        }
+    }
+
+    /// Returns the nearest standard color.
+    pub fn to_named(self) -> NamedColor {
+        match self {
+            Color::Named(named) => named,
+            Color::Ansi256(index) if index < 16 => NamedColor::ALL[usize::from(index)],
+            _ => {
+                let rgb = self.to_rgb();
+                NamedColor::ALL
+                    .iter()
+                    .copied()
+                    .min_by_key(|named| distance(named.to_rgb(), rgb))
+                    .unwrap()
+            }
+        }
+    }
+}
+
+/// The colors a terminal is able to display.
+///
+/// The variants are ordered from the most limited to the richest support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum ColorSupport {
+    /// No color at all.
+    NoColor,
+    /// The 16 standard colors.
+    Ansi16,
+    /// The 256 colors palette.
+    Ansi256,
+    /// True colors.
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Guesses the color support of the terminal from the environment: `NO_COLOR`, `COLORTERM`
+    /// and `TERM`.
+    pub fn from_env() -> ColorSupport {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        if !var("NO_COLOR").is_empty() {
+            return ColorSupport::NoColor;
+        }
+        let colorterm = var("COLORTERM");
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+        let term = var("TERM");
+        if term.is_empty() || term == "dumb" {
+            ColorSupport::NoColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+}
+
+impl Color {
+    /// Returns the nearest color displayable with the given support, `None` if colors are not
+    /// supported. Colors already supported are returned as is.
+    ///
+    /// ```
+    /// use ornament::color::{Color, ColorSupport, NamedColor};
+    ///
+    /// let orange = Color::Rgb(255, 135, 0);
+    /// assert_eq!(orange.downgrade(ColorSupport::TrueColor), Some(orange));
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi256), Some(Color::Ansi256(208)));
+    /// assert_eq!(
+    ///     orange.downgrade(ColorSupport::Ansi16),
+    ///     Some(Color::Named(NamedColor::Yellow))
+    /// );
+    /// assert_eq!(orange.downgrade(ColorSupport::NoColor), None);
+    /// ```
+    pub fn downgrade(self, support: ColorSupport) -> Option<Color> {
+        match (support, self) {
+            (ColorSupport::NoColor, _) => None,
+            (ColorSupport::Ansi16, Color::Named(_)) | (ColorSupport::Ansi256, Color::Named(_)) => {
+                Some(self)
+            }
+            (ColorSupport::Ansi16, _) => Some(Color::Named(self.to_named())),
+            (ColorSupport::Ansi256, _) => Some(Color::Ansi256(self.to_ansi256())),
+            (ColorSupport::TrueColor, _) => Some(self),
+        }
+    }
+}
+
+impl From<NamedColor> for Color {
+    fn from(named: NamedColor) -> Color {
+        Color::Named(named)
+    }
+}
+
+impl From<u8> for Color {
+    fn from(index: u8) -> Color {
+        Color::Ansi256(index)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Color {
+        Color::Rgb(r, g, b)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::Text;
+use ::colored::{Color, ColoredString, Colorize};
+
+/// A face which can be displayed with the `colored` crate.
+///
+/// As everything printed with `colored`, the rendering follows its global color control (the
+/// `NO_COLOR` and `CLICOLOR` variables or `colored::control::set_override`).
+///
+/// ```
+/// use colored::{ColoredString, Colorize};
+/// use ornament::{ColoredFace, Decorator};
+///
+/// #[derive(Clone, Debug, Default, PartialEq)]
+/// enum Face {
+///     #[default]
+///     Plain,
+///     Error,
+/// }
+///
+/// impl ColoredFace for Face {
+///     fn colorize(&self, text: &str) -> ColoredString {
+///         match self {
+///             Face::Plain => text.normal(),
+///             Face::Error => text.red(),
+///         }
+///     }
+/// }
+///
+/// colored::control::set_override(true);
+/// let text = Decorator::with_text("error: not found").set(Face::Error, 0..5).build();
+/// assert_eq!(text.render_colored(), "\x1b[31merror\x1b[0m: not found");
+/// ```
+pub trait ColoredFace {
+    /// Returns `text` colored with the face.
+    fn colorize(&self, text: &str) -> ColoredString;
+}
+
+impl ColoredFace for Color {
+    fn colorize(&self, text: &str) -> ColoredString {
+        text.color(*self)
+    }
+}
+
+impl<F: ColoredFace> Text<F> {
+    /// Renders the decorated text with the `colored` strings of each face.
+    pub fn render_colored(&self) -> String {
+        self.render(|tf| tf.face.colorize(&tf.text).to_string())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Decorator, Text};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Encodes the text on a single line, for snapshot tests: the plain text followed by a
+    /// `‹face:start..end›` tag per fragment which doesn't have the default face. Faces are
+    /// formatted with `Display` and ranges are in bytes.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Text};
+    ///
+    /// let text = Decorator::with_text("Text can be with emphasis or even strong.")
+    ///     .set("Emphasis".to_owned(), 17..25)
+    ///     .set("Strong".to_owned(), 34..40)
+    ///     .build();
+    /// let compact = text.to_compact_string();
+    /// assert_eq!(
+    ///     compact,
+    ///     "Text can be with emphasis or even strong.‹Emphasis:17..25›‹Strong:34..40›"
+    /// );
+    /// assert_eq!(Text::from_compact_str(&compact), Some(text));
+    /// ```
+    pub fn to_compact_string(&self) -> String
+    where
+        F: Display,
+    {
+        let default = F::default();
+        let mut out = self.plain();
+        for (range, tf) in self.fragments_with_offsets() {
+            if tf.face != default {
+                let _ = write!(out, "‹{}:{}..{}›", tf.face, range.start, range.end);
+            }
+        }
+        out
+    }
+
+    /// Creates a text from its encoding by [`to_compact_string`], faces being parsed with
+    /// `FromStr`. Returns `None` if a tag is malformed or its range is not within the text.
+    ///
+    /// As tags are read from the end, a plain text ending with a tag-like sequence can't be
+    /// decoded back.
+    ///
+    /// [`to_compact_string`]: struct.Text.html#method.to_compact_string
+    pub fn from_compact_str(s: &str) -> Option<Text<F>>
+    where
+        F: FromStr,
+    {
+        let mut plain = s;
+        let mut tags = Vec::new();
+        while let Some(rest) = plain.strip_suffix('›') {
+            let (rest, tag) = rest.rsplit_once('‹')?;
+            tags.push(tag);
+            plain = rest;
+        }
+
+        let mut decorator = Decorator::with_text(plain);
+        for tag in tags.into_iter().rev() {
+            let (face, range) = tag.rsplit_once(':')?;
+            let (start, end) = range.split_once("..")?;
+            let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+            if start >= end
+                || end > plain.len()
+                || !plain.is_char_boundary(start)
+                || !plain.is_char_boundary(end)
+            {
+                return None;
+            }
+            decorator.set(face.parse().ok()?, start..end);
+        }
+        Some(decorator.build())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::decorations::Decorations;
+use crate::{Buffer, Decorator};
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Appends the text of `other` with its decorations, priorities and annotations. The
+    /// current face is left untouched and the cursors of `other` are dropped.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut header = Decorator::with_text("# Title\n");
+    /// header.set("title", 2..7);
+    /// let mut body = Decorator::with_text("Some text.");
+    /// body.set("em", 5..9);
+    /// let text = header.absorb(body).build();
+    /// assert_eq!(text.plain(), "# Title\nSome text.");
+    /// assert_eq!(text.face_at(2), Some(&"title"));
+    /// assert_eq!(text.face_at(13), Some(&"em"));
+    /// ```
+    pub fn absorb(&mut self, other: Decorator<F, B>) -> &mut Self {
+        let offset = self.text.len();
+        let len = other.text.len();
+        if len == 0 {
+            return self;
+        }
+        self.edited(offset..offset, len);
+        self.text.push_str(&other.text.slice(0..len));
+        for (face, run) in other.decorations.flatten_from(0) {
+            self.decorations.append(face, run);
+        }
+        match (&mut self.priorities, other.priorities) {
+            (Some(priorities), Some(others)) => {
+                for (priority, run) in others.flatten_from(0) {
+                    priorities.append(priority, run);
+                }
+            }
+            (Some(priorities), None) => priorities.append(0, len),
+            (None, Some(others)) => {
+                let mut priorities = Decorations::new(0);
+                priorities.append(0, offset);
+                for (priority, run) in others.flatten_from(0) {
+                    priorities.append(priority, run);
+                }
+                self.priorities = Some(priorities);
+            }
+            (None, None) => {}
+        }
+        for mut annotation in other.annotations {
+            annotation.shift(offset);
+            self.annotations.push(annotation);
+        }
+        self
+    }
+
+    /// Consumes both decorators and returns the concatenation of `self` and `other`, as
+    /// [`absorb`] does.
+    ///
+    /// [`absorb`]: struct.Decorator.html#method.absorb
+    pub fn concat(mut self, other: Decorator<F, B>) -> Decorator<F, B> {
+        self.absorb(other);
+        self
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{Buffer, Decorator};
+use std::ops::Range;
+
+/// Where a [`Cursor`] goes when text is inserted at its position.
+///
+/// [`Cursor`]: struct.Cursor.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Bias {
+    /// The cursor stays before the inserted text.
+    #[default]
+    Before,
+    /// The cursor moves after the inserted text.
+    After,
+}
+
+/// A handle to a position in a [`Decorator`], adjusted by the edits of its text.
+///
+/// A cursor is created with [`Decorator::cursor`] and its current position is given by
+/// [`Decorator::position`]. A cursor inside a removed range moves to the edge of the replacement
+/// given by its [`Bias`].
+///
+/// ```
+/// use ornament::{Bias, Decorator};
+///
+/// let mut decorator = Decorator::<()>::with_text("Hello");
+/// let start = decorator.cursor(0, Bias::Before);
+/// let end = decorator.cursor(5, Bias::After);
+/// decorator.append(", world");
+/// assert_eq!(decorator.position(start), Some(0));
+/// assert_eq!(decorator.position(end), Some(12));
+///
+/// decorator.release(end);
+/// assert_eq!(decorator.position(end), None);
+/// ```
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`Decorator::cursor`]: struct.Decorator.html#method.cursor
+/// [`Decorator::position`]: struct.Decorator.html#method.position
+/// [`Bias`]: enum.Bias.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cursor(usize);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CursorSlot {
+    offset: usize,
+    bias: Bias,
+}
+
+impl CursorSlot {
+    /// Moves the position after `range` is replaced by `inserted` bytes.
+    pub(crate) fn adjust(&mut self, range: &Range<usize>, inserted: usize) {
+        if self.offset < range.start || (self.offset == range.start && self.bias == Bias::Before) {
+            return;
+        }
+        self.offset = if self.offset >= range.end {
+            self.offset - range.len() + inserted
+        } else {
+            match self.bias {
+                Bias::Before => range.start,
+                Bias::After => range.start + inserted,
+            }
+        };
+    }
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Creates a cursor at `offset`, clamped to the length of the text.
+    pub fn cursor(&mut self, offset: usize, bias: Bias) -> Cursor {
+        let slot = CursorSlot {
+            offset: offset.min(self.text.len()),
+            bias,
+        };
+        match self.cursors.iter().position(Option::is_none) {
+            Some(idx) => {
+                self.cursors[idx] = Some(slot);
+                Cursor(idx)
+            }
+            None => {
+                self.cursors.push(Some(slot));
+                Cursor(self.cursors.len() - 1)
+            }
+        }
+    }
+
+    /// Returns the current position of `cursor`, `None` if it has been released.
+    pub fn position(&self, cursor: Cursor) -> Option<usize> {
+        self.cursors
+            .get(cursor.0)
+            .copied()
+            .flatten()
+            .map(|slot| slot.offset)
+    }
+
+    /// Releases `cursor`, it is no longer adjusted and its handle may be reused by a new cursor.
+    pub fn release(&mut self, cursor: Cursor) {
+        if let Some(slot) = self.cursors.get_mut(cursor.0) {
+            *slot = None;
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::cmp::{max, min};
+use std::collections::BTreeSet;
 use std::ops::Range;
 
 #[derive(Clone)]
@@ -12,10 +14,7 @@ where
 {
     fn is_range(&self) -> bool {
         use TextRange::*;
-        match self {
-            Range(_) => true,
-            _ => false,
-        }
+        matches!(self, Range(_))
     }
 
     fn is_decoration_face(&self, face: &F) -> bool {
@@ -135,7 +134,7 @@ where
 
     pub(crate) fn append(&mut self, face: F, len: usize) {
         if self.face == face {
-            let last_is_range = self.fragments.last().map_or(false, TextRange::is_range);
+            let last_is_range = self.fragments.last().is_some_and(TextRange::is_range);
             if last_is_range {
                 let old_len = match self.fragments.pop() {
                     Some(TextRange::Range(len)) => len,
@@ -149,7 +148,7 @@ where
             let last_is_face = self
                 .fragments
                 .last()
-                .map_or(false, |tf| tf.is_decoration_face(&face));
+                .is_some_and(|tf| tf.is_decoration_face(&face));
             if last_is_face {
                 match self.fragments.last_mut() {
                     Some(TextRange::Decoration(d)) => d.append(face.clone(), len),
@@ -168,7 +167,7 @@ where
 
         if start == end {
             if let TextRange::Decoration(d) = &mut self.fragments[start] {
-                d.set(face, range);
+                d.set(face, range.start - start_offset..range.end - start_offset);
                 return;
             }
         }
@@ -187,15 +186,182 @@ where
         self.fragments.splice(start..=end, new_fragments);
     }
 
-    pub(crate) fn flatten(&self) -> Vec<(F, usize)> {
+    pub(crate) fn faces_in(&self, range: Range<usize>) -> Vec<(F, Range<usize>)> {
         let mut acc = Vec::new();
-        for frag in &self.fragments {
+        let mut pos = range.start;
+        for (face, len) in self.flatten_from(range.start) {
+            if pos >= range.end {
+                break;
+            }
+            let end = min(pos + len, range.end);
+            acc.push((face, pos..end));
+            pos = end;
+        }
+        acc
+    }
+
+    pub(crate) fn node_count(&self) -> usize {
+        self.fragments.iter().fold(1, |acc, item| {
             use TextRange::*;
-            match frag {
-                Range(len) => acc.push((self.face.clone(), *len)),
-                Decoration(d) => acc.append(&mut d.flatten()),
+            match item {
+                Range(_) => acc + 1,
+                Decoration(d) => acc + d.node_count(),
+            }
+        })
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        1 + self
+            .fragments
+            .iter()
+            .map(|item| match item {
+                TextRange::Decoration(d) => d.depth(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        let own = self.fragments.capacity() * std::mem::size_of::<TextRange<F>>();
+        self.fragments.iter().fold(own, |acc, item| match item {
+            TextRange::Decoration(d) => acc + d.allocated_bytes(),
+            _ => acc,
+        })
+    }
+
+    /// Removes the decorations after `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if len == 0 {
+            self.fragments.clear();
+            return;
+        }
+        let (idx, idx_offset) = self.fragment_index_of(len).expect("invalid offset");
+        let last = self.fragments[idx].keep_start(len - idx_offset);
+        self.fragments.truncate(idx);
+        self.fragments.extend(last);
+    }
+
+    /// Removes all the decorations, keeping the allocated capacity of the root.
+    pub(crate) fn clear(&mut self) {
+        self.fragments.clear();
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.fragments.shrink_to_fit();
+        for frag in &mut self.fragments {
+            if let TextRange::Decoration(d) = frag {
+                d.shrink_to_fit();
+            }
+        }
+    }
+
+    pub(crate) fn flatten_from(&self, offset: usize) -> Vec<(F, usize)> {
+        let mut acc = Vec::new();
+        let mut pos = 0;
+        for frag in &self.fragments {
+            let len = frag.len();
+            if pos + len > offset {
+                use TextRange::*;
+                match frag {
+                    Range(len) => acc.push((self.face.clone(), pos + len - max(pos, offset))),
+                    Decoration(d) => acc.append(&mut d.flatten_from(offset.saturating_sub(pos))),
+                }
             }
+            pos += len;
         }
         acc
     }
+
+    /// Returns the decorations after replacing each of the sorted and non-overlapping `edits`
+    /// ranges by the given length. Replacements take the face found at the start of their range,
+    /// insertions the face of the byte before them.
+    pub(crate) fn spliced(&self, edits: &[(Range<usize>, usize)]) -> Decorations<F> {
+        let runs = self.flatten_from(0);
+        let mut starts = Vec::with_capacity(runs.len());
+        let mut len = 0;
+        for (_, run_len) in &runs {
+            starts.push(len);
+            len += run_len;
+        }
+        let run_at = |offset: usize| starts.partition_point(|&start| start <= offset).max(1) - 1;
+        let face_at = |offset: usize| {
+            runs.get(run_at(offset))
+                .map_or_else(|| self.face.clone(), |(face, _)| face.clone())
+        };
+
+        let mut new = Decorations::new(self.face.clone());
+        let copy = |new: &mut Decorations<F>, from: usize, to: usize| {
+            let mut pos = from;
+            while pos < to {
+                let idx = run_at(pos);
+                let end = min(starts[idx] + runs[idx].1, to);
+                new.append(runs[idx].0.clone(), end - pos);
+                pos = end;
+            }
+        };
+        let mut pos = 0;
+        for (range, inserted) in edits {
+            copy(&mut new, pos, range.start);
+            if *inserted > 0 {
+                let face = if range.is_empty() && range.start > 0 {
+                    face_at(range.start - 1)
+                } else {
+                    face_at(range.start)
+                };
+                new.append(face, *inserted);
+            }
+            pos = range.end;
+        }
+        copy(&mut new, pos, len);
+        new
+    }
+
+    /// Returns the decorations after assigning each face to its range in order, as successive
+    /// calls to `set` would, the last assignation winning where ranges overlap. Ranges must be
+    /// valid and non-empty.
+    pub(crate) fn painted(&self, ranges: &[(F, Range<usize>)]) -> Decorations<F> {
+        let runs = self.flatten_from(0);
+        let len = self.len();
+        let mut events = Vec::with_capacity(ranges.len() * 2);
+        for (idx, (_, range)) in ranges.iter().enumerate() {
+            events.push((range.start, true, idx));
+            events.push((range.end, false, idx));
+        }
+        events.sort_unstable_by_key(|&(offset, _, _)| offset);
+        let mut bounds: Vec<usize> = events.iter().map(|&(offset, _, _)| offset).collect();
+        let mut start = 0;
+        for (_, run_len) in &runs {
+            bounds.push(start);
+            start += run_len;
+        }
+        bounds.push(len);
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut new = Decorations::new(self.face.clone());
+        let mut active = BTreeSet::new();
+        let mut events = events.into_iter().peekable();
+        let (mut run, mut run_end) = (0, runs.first().map_or(0, |(_, len)| *len));
+        for window in bounds.windows(2) {
+            let (from, to) = (window[0], window[1]);
+            while let Some((_, is_start, idx)) = events.next_if(|&(offset, _, _)| offset <= from) {
+                if is_start {
+                    active.insert(idx);
+                } else {
+                    active.remove(&idx);
+                }
+            }
+            while run_end <= from {
+                run += 1;
+                run_end += runs[run].1;
+            }
+            let face = match active.last() {
+                Some(&idx) => ranges[idx].0.clone(),
+                None => runs[run].0.clone(),
+            };
+            new.append(face, to - from);
+        }
+        new
+    }
 }
@@ -0,0 +1,237 @@
+//! Differences between plain strings as decorated texts.
+//!
+//! Unlike the [`testing`] helpers, the diff is inline: the removed and added pieces are kept in
+//! place, without prefixes, and only told apart by their [`DiffFace`].
+//!
+//! ```
+//! use ornament::diff::{diff, DiffFace, Granularity};
+//!
+//! let text = diff("let x = 1;", "let y = 1;", Granularity::Word);
+//! assert_eq!(text.plain(), "let xy = 1;");
+//! assert_eq!(text.face_at(4), Some(&DiffFace::Removed));
+//! assert_eq!(text.face_at(5), Some(&DiffFace::Added));
+//! ```
+//!
+//! [`testing`]: ../testing/index.html
+//! [`DiffFace`]: enum.DiffFace.html
+
+use crate::{Decorator, Text};
+
+/// The faces of a diff.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum DiffFace {
+    /// Present in both sides.
+    #[default]
+    Equal,
+    /// Only present in the new side.
+    Added,
+    /// Only present in the old side.
+    Removed,
+}
+
+/// The unit compared by a diff.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Granularity {
+    /// Lines, including their line break.
+    #[default]
+    Line,
+    /// Words, runs of whitespace and single punctuation characters.
+    Word,
+}
+
+impl Granularity {
+    fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            Granularity::Line => text.split_inclusive('\n').collect(),
+            Granularity::Word => {
+                let mut tokens = Vec::new();
+                let mut start = 0;
+                let mut previous = None;
+                for (idx, c) in text.char_indices() {
+                    let class = if c.is_alphanumeric() || c == '_' {
+                        Some(0)
+                    } else if c.is_whitespace() {
+                        Some(1)
+                    } else {
+                        None
+                    };
+                    if idx > 0 && (class.is_none() || class != previous) {
+                        tokens.push(&text[start..idx]);
+                        start = idx;
+                    }
+                    previous = class;
+                }
+                if start < text.len() {
+                    tokens.push(&text[start..]);
+                }
+                tokens
+            }
+        }
+    }
+}
+
+/// Returns the inline diff of `old` and `new` compared by `granularity`: pieces present in both
+/// are decorated with [`DiffFace::Equal`], the removed ones with [`DiffFace::Removed`] and the
+/// added ones with [`DiffFace::Added`]. Removed pieces come before the pieces replacing them.
+///
+/// ```
+/// use ornament::diff::{diff, DiffFace, Granularity};
+///
+/// let text = diff("a\nb\n", "a\nc\n", Granularity::Line);
+/// assert_eq!(text.plain(), "a\nb\nc\n");
+/// assert_eq!(text.face_at(2), Some(&DiffFace::Removed));
+/// assert_eq!(text.face_at(4), Some(&DiffFace::Added));
+/// ```
+///
+/// [`DiffFace::Equal`]: enum.DiffFace.html#variant.Equal
+/// [`DiffFace::Removed`]: enum.DiffFace.html#variant.Removed
+/// [`DiffFace::Added`]: enum.DiffFace.html#variant.Added
+pub fn diff(old: &str, new: &str, granularity: Granularity) -> Text<DiffFace> {
+    let old = granularity.split(old);
+    let new = granularity.split(new);
+    let mut decorator = Decorator::new();
+    for (face, piece) in diff_slices(&old, &new) {
+        decorator.set_face(face).append(piece);
+    }
+    decorator.build()
+}
+
+/// Returns the items of `old` and `new` in diff order with their face, removals coming before
+/// the additions they are replaced by.
+///
+/// The diff is computed with the linear space variant of Myers' algorithm.
+pub(crate) fn diff_slices<'a, T: PartialEq>(old: &'a [T], new: &'a [T]) -> Vec<(DiffFace, &'a T)> {
+    let max = old.len() + new.len() + 1;
+    let mut paths = Paths {
+        forward: vec![0; 2 * max + 1],
+        backward: vec![0; 2 * max + 1],
+        offset: max as isize,
+    };
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    paths.conquer(old, new, &mut ops);
+
+    let mut start = 0;
+    while start < ops.len() {
+        let mut len = ops[start..]
+            .iter()
+            .take_while(|(face, _)| *face != DiffFace::Equal)
+            .count();
+        if len > 0
+            && ops[start..start + len]
+                .iter()
+                .all(|op| op.0 == ops[start].0)
+        {
+            // Slide the runs of a single kind past the unchanged items repeating their start,
+            // e.g. `a{+, b: u8+}` rather than `a{+: u8, b+}: u8`.
+            while ops
+                .get(start + len)
+                .is_some_and(|op| op.0 == DiffFace::Equal && op.1 == ops[start].1)
+            {
+                ops[start + len].0 = ops[start].0;
+                ops[start].0 = DiffFace::Equal;
+                start += 1;
+            }
+            len = ops[start..]
+                .iter()
+                .take_while(|(face, _)| *face != DiffFace::Equal)
+                .count();
+        }
+        // Removals come before the additions of the run.
+        ops[start..start + len].sort_by_key(|(face, _)| *face != DiffFace::Removed);
+        start += len.max(1);
+    }
+    ops
+}
+
+/// The furthest reaching paths of the forward and backward searches, indexed by diagonal.
+struct Paths {
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+    offset: isize,
+}
+
+impl Paths {
+    fn conquer<'a, T: PartialEq>(
+        &mut self,
+        old: &'a [T],
+        new: &'a [T],
+        ops: &mut Vec<(DiffFace, &'a T)>,
+    ) {
+        let prefix = common_len(old.iter(), new.iter());
+        ops.extend(old[..prefix].iter().map(|item| (DiffFace::Equal, item)));
+        let (old, new) = (&old[prefix..], &new[prefix..]);
+        let suffix = common_len(old.iter().rev(), new.iter().rev());
+        let (old, tail) = old.split_at(old.len() - suffix);
+        let new = &new[..new.len() - suffix];
+
+        if old.is_empty() {
+            ops.extend(new.iter().map(|item| (DiffFace::Added, item)));
+        } else if new.is_empty() {
+            ops.extend(old.iter().map(|item| (DiffFace::Removed, item)));
+        } else {
+            let (x, y) = self.middle_snake(old, new);
+            self.conquer(&old[..x], &new[..y], ops);
+            self.conquer(&old[x..], &new[y..], ops);
+        }
+        ops.extend(tail.iter().map(|item| (DiffFace::Equal, item)));
+    }
+
+    /// Returns the start of the middle snake of an optimal path between `old` and `new`, which
+    /// must differ by their first and last items.
+    fn middle_snake<T: PartialEq>(&mut self, old: &[T], new: &[T]) -> (usize, usize) {
+        let (n, m) = (old.len(), new.len());
+        let delta = n as isize - m as isize;
+        let odd = delta % 2 != 0;
+        let (forward, backward) = (&mut self.forward, &mut self.backward);
+        let offset = self.offset;
+        let at = |k: isize| (k + offset) as usize;
+        forward[at(1)] = 0;
+        backward[at(1)] = 0;
+        for d in 0..=(n + m) as isize {
+            for k in (-d..=d).step_by(2) {
+                let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                    forward[at(k + 1)]
+                } else {
+                    forward[at(k - 1)] + 1
+                };
+                let y = (x as isize - k) as usize;
+                let start = (x, y);
+                if x < n && y < m {
+                    x += common_len(old[x..].iter(), new[y..].iter());
+                }
+                forward[at(k)] = x;
+                if odd && (k - delta).abs() < d && x + backward[at(delta - k)] >= n {
+                    return start;
+                }
+            }
+            // The backward search runs from the ends, `x` and `y` count the items left out.
+            for k in (-d..=d).step_by(2) {
+                let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                    backward[at(k + 1)]
+                } else {
+                    backward[at(k - 1)] + 1
+                };
+                let mut y = (x as isize - k) as usize;
+                if x < n && y < m {
+                    let common = common_len(old[..n - x].iter().rev(), new[..m - y].iter().rev());
+                    x += common;
+                    y += common;
+                }
+                backward[at(k)] = x;
+                if !odd && (k - delta).abs() <= d && x + forward[at(delta - k)] >= n {
+                    return (n - x, m - y);
+                }
+            }
+        }
+        unreachable!("no middle snake between the sequences")
+    }
+}
+
+fn common_len<'a, T: PartialEq + 'a>(
+    old: impl Iterator<Item = &'a T>,
+    new: impl Iterator<Item = &'a T>,
+) -> usize {
+    old.zip(new).take_while(|(a, b)| a == b).count()
+}
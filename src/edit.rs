@@ -0,0 +1,187 @@
+use crate::{bounded, Buffer, Decorator};
+use std::cmp::min;
+use std::ops::{Range, RangeBounds};
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Replaces each range of `edits` by its text, as a single operation. Ranges are given
+    /// relatively to the text before the edits, in any order, like LSP `TextEdit`s.
+    ///
+    /// Decorations are adjusted once for all the edits: a replacement takes the face found at the
+    /// start of its range and an insertion the face of the text before it. Cursors and
+    /// annotations follow the edits. Adjusting the decorations rebuilds them, which takes a time
+    /// linear in the number of decorated fragments whatever the [`Buffer`] is.
+    ///
+    /// This method is chainable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two ranges overlap or if a bound is not on a `char` boundary. Ranges are clamped
+    /// to the length of the text.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = foo(x);")
+    ///     .set("call", 8..11)
+    ///     .apply_edits(&[(12..13, "y".to_owned()), (4..5, "y".to_owned()), (8..11, "bar".to_owned())])
+    ///     .build();
+    /// assert_eq!(text.plain(), "let y = bar(y);");
+    /// assert_eq!(text.face_at(8), Some(&"call"));
+    /// ```
+    ///
+    /// [`Buffer`]: trait.Buffer.html
+    pub fn apply_edits(&mut self, edits: &[(Range<usize>, String)]) -> &mut Self {
+        let len = self.text.len();
+        let mut sorted: Vec<(Range<usize>, &str)> = edits
+            .iter()
+            .map(|(range, text)| {
+                let end = min(range.end, len);
+                (min(range.start, end)..end, text.as_str())
+            })
+            .collect();
+        // Insertions come before the replacements starting at the same offset.
+        sorted.sort_by_key(|(range, _)| (range.start, range.end));
+        for pair in sorted.windows(2) {
+            assert!(
+                pair[0].0.end <= pair[1].0.start,
+                "overlapping edits: {:?} and {:?}",
+                pair[0].0,
+                pair[1].0
+            );
+        }
+        if sorted.is_empty() {
+            return self;
+        }
+
+        let lengths: Vec<(Range<usize>, usize)> = sorted
+            .iter()
+            .map(|(range, replacement)| (range.clone(), replacement.len()))
+            .collect();
+        self.revision += 1;
+        self.checkpoints.clear();
+        self.decorations = self.decorations.spliced(&lengths);
+        if let Some(priorities) = &self.priorities {
+            self.priorities = Some(priorities.spliced(&lengths));
+        }
+        self.text.replace_ranges(&sorted);
+        // Later edits first, so that the ranges of the others stay valid.
+        for (range, inserted) in lengths.into_iter().rev() {
+            self.edited(range, inserted);
+        }
+        self
+    }
+
+    /// Inserts `text` at `offset` and assigns it the current face. The decorations after
+    /// `offset` are shifted, `offset` is clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is not on a `char` boundary.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("Hello !")
+    ///     .set("punct", 6..7)
+    ///     .set_face("name")
+    ///     .insert(6, "world")
+    ///     .build();
+    /// assert_eq!(text.plain(), "Hello world!");
+    /// assert_eq!(text.face_at(6), Some(&"name"));
+    /// assert_eq!(text.face_at(11), Some(&"punct"));
+    /// ```
+    pub fn insert(&mut self, offset: usize, text: &str) -> &mut Self {
+        let face = self.current_face().clone();
+        self.insert_with_face(offset, text, face)
+    }
+
+    /// Inserts `text` at `offset` as [`insert`] does, and assigns it `face`.
+    ///
+    /// This method is chainable.
+    ///
+    /// [`insert`]: struct.Decorator.html#method.insert
+    pub fn insert_with_face(&mut self, offset: usize, text: &str, face: F) -> &mut Self {
+        let offset = min(offset, self.text.len());
+        self.apply_edits(&[(offset..offset, text.to_owned())]);
+        self.assign(face, offset..offset + text.len());
+        self
+    }
+
+    /// Removes the text in the given range with its decorations, the decorations after it are
+    /// shifted. The range is clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a bound of the range is not on a `char` boundary.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("[INFO] [boilerplate] done")
+    ///     .set("level", 1..5)
+    ///     .set("status", 21..25)
+    ///     .remove(7..21)
+    ///     .build();
+    /// assert_eq!(text.plain(), "[INFO] done");
+    /// assert_eq!(text.face_at(7), Some(&"status"));
+    /// ```
+    pub fn remove<R: RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        let range = bounded(range, self.text.len());
+        self.apply_edits(&[(range, String::new())])
+    }
+
+    /// Replaces the text in the given range by `text` and assigns it the current face. The
+    /// decorations around the range are kept and the ones after it are shifted. The range is
+    /// clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a bound of the range is not on a `char` boundary.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("Dear {name},")
+    ///     .set("greeting", 0..4)
+    ///     .set_face("name")
+    ///     .replace_range(5..11, "Ada")
+    ///     .build();
+    /// assert_eq!(text.plain(), "Dear Ada,");
+    /// assert_eq!(text.face_at(0), Some(&"greeting"));
+    /// assert_eq!(text.face_at(5), Some(&"name"));
+    /// assert_eq!(text.face_at(8), Some(&""));
+    /// ```
+    pub fn replace_range<R: RangeBounds<usize>>(&mut self, range: R, text: &str) -> &mut Self {
+        let face = self.current_face().clone();
+        self.replace_range_with_face(range, text, face)
+    }
+
+    /// Replaces the text in the given range as [`replace_range`] does, and assigns `face` to the
+    /// replacement.
+    ///
+    /// This method is chainable.
+    ///
+    /// [`replace_range`]: struct.Decorator.html#method.replace_range
+    pub fn replace_range_with_face<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+        text: &str,
+        face: F,
+    ) -> &mut Self {
+        let range = bounded(range, self.text.len());
+        let start = min(range.start, range.end);
+        self.apply_edits(&[(range, text.to_owned())]);
+        self.assign(face, start..start + text.len());
+        self
+    }
+}
@@ -0,0 +1,79 @@
+/// A face that can be combined with another one.
+///
+/// This is used by the combining methods of the [`Decorator`] (e.g. [`overlay`]) and by [`set`]
+/// in the combining mode (see [`combining`]) to layer a face on top of the ones already
+/// assigned, instead of replacing them.
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`overlay`]: struct.Decorator.html#method.overlay
+/// [`set`]: struct.Decorator.html#method.set
+/// [`combining`]: struct.Decorator.html#method.combining
+pub trait FaceMerge {
+    /// Returns the combination of `self` with `other` layered on top of it.
+    fn merge(&self, other: &Self) -> Self;
+}
+
+/// A face that can inherit from a parent face.
+///
+/// This is used to model face hierarchies: when the [`Theme`] has no style for a face, the style
+/// of its nearest ancestor is used instead (see [`Theme::resolve_inherited`]).
+///
+/// ```
+/// use ornament::FaceResolve;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Group(String);
+///
+/// impl FaceResolve for Group {
+///     fn parent(&self) -> Option<Self> {
+///         let (parent, _) = self.0.rsplit_once('.')?;
+///         Some(Group(parent.to_owned()))
+///     }
+/// }
+///
+/// let doc = Group("comment.doc".to_owned());
+/// assert_eq!(doc.parent(), Some(Group("comment".to_owned())));
+/// ```
+///
+/// [`Theme`]: struct.Theme.html
+/// [`Theme::resolve_inherited`]: struct.Theme.html#method.resolve_inherited
+pub trait FaceResolve: Sized {
+    /// Returns the parent face, or `None` if the face is at the root of the hierarchy.
+    fn parent(&self) -> Option<Self>;
+}
+
+/// A face rendered by wrapping the text between delimiters.
+///
+/// This is used by [`Text::render_wrapped`] to render the text without writing a decorator.
+///
+/// ```
+/// use ornament::{Decorator, FaceWrap};
+///
+/// #[derive(Clone, Debug, Default, PartialEq)]
+/// struct Code(bool);
+///
+/// impl FaceWrap for Code {
+///     fn prefix(&self) -> &str {
+///         if self.0 {
+///             "`"
+///         } else {
+///             ""
+///         }
+///     }
+///
+///     fn suffix(&self) -> &str {
+///         self.prefix()
+///     }
+/// }
+///
+/// let text = Decorator::with_text("run cargo test").set(Code(true), 4..14).build();
+/// assert_eq!(text.render_wrapped(), "run `cargo test`");
+/// ```
+///
+/// [`Text::render_wrapped`]: struct.Text.html#method.render_wrapped
+pub trait FaceWrap {
+    /// Returns the text rendered before a fragment with this face.
+    fn prefix(&self) -> &str;
+    /// Returns the text rendered after a fragment with this face.
+    fn suffix(&self) -> &str;
+}
@@ -0,0 +1,183 @@
+//! A C API over the decoration engine, with faces identified by `u32` IDs, `0` being the
+//! default face.
+//!
+//! The functions are exported unmangled, a `cdylib` crate depending on `ornament` with the
+//! `ffi` feature exposes them. Decorators and texts are opaque pointers which must be released
+//! with [`ornament_decorator_free`] and [`ornament_text_free`].
+//!
+//! ```c
+//! OrnamentDecorator *decorator = ornament_decorator_new();
+//! ornament_decorator_append(decorator, (const uint8_t *)"let x = 1;", 10, 0);
+//! ornament_decorator_set(decorator, 1, 0, 3);
+//! OrnamentText *text = ornament_decorator_build(decorator);
+//! ornament_decorator_free(decorator);
+//!
+//! OrnamentFragment fragment;
+//! for (size_t i = 0; ornament_text_fragment(text, i, &fragment); i++) {
+//!     printf("%u: %.*s\n", fragment.face_id, (int)fragment.len, fragment.ptr);
+//! }
+//! ornament_text_free(text);
+//! ```
+//!
+//! [`ornament_decorator_free`]: fn.ornament_decorator_free.html
+//! [`ornament_text_free`]: fn.ornament_text_free.html
+
+use crate::{Decorator, Text};
+use std::ptr;
+use std::slice;
+use std::str;
+
+/// An opaque decorator, with `u32` face IDs.
+pub type OrnamentDecorator = Decorator<u32>;
+
+/// An opaque decorated text, with `u32` face IDs.
+pub type OrnamentText = Text<u32>;
+
+/// A fragment of a text, borrowed from it. `ptr` points to `len` bytes of UTF-8 text, which are
+/// not nul-terminated.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct OrnamentFragment {
+    /// The start of the fragment text.
+    pub ptr: *const u8,
+    /// The length of the fragment text, in bytes.
+    pub len: usize,
+    /// The face ID of the fragment.
+    pub face_id: u32,
+}
+
+/// Creates a new empty decorator.
+#[no_mangle]
+pub extern "C" fn ornament_decorator_new() -> *mut OrnamentDecorator {
+    Box::into_raw(Box::new(Decorator::new()))
+}
+
+/// Releases a decorator. Does nothing if `decorator` is null.
+///
+/// # Safety
+///
+/// `decorator` must be null or returned by [`ornament_decorator_new`], and not released yet.
+///
+/// [`ornament_decorator_new`]: fn.ornament_decorator_new.html
+#[no_mangle]
+pub unsafe extern "C" fn ornament_decorator_free(decorator: *mut OrnamentDecorator) {
+    if !decorator.is_null() {
+        drop(Box::from_raw(decorator));
+    }
+}
+
+/// Appends the `len` bytes at `text` with the face `face_id`. Returns `false`, without appending
+/// anything, if they are not valid UTF-8.
+///
+/// # Safety
+///
+/// `decorator` must be a valid decorator and `text` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ornament_decorator_append(
+    decorator: *mut OrnamentDecorator,
+    text: *const u8,
+    len: usize,
+    face_id: u32,
+) -> bool {
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(text, len)
+    };
+    match str::from_utf8(bytes) {
+        Ok(text) => {
+            (*decorator).set_face(face_id).append(text).reset_face();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Assigns the face `face_id` to the byte range `start..end`, as [`Decorator::set`] does.
+/// Returns `false`, without assigning anything, if the range is invalid.
+///
+/// # Safety
+///
+/// `decorator` must be a valid decorator. The range is checked: `start` must not be greater than
+/// `end`, `end` must not be greater than the length of the text and both must be on UTF-8
+/// character boundaries.
+///
+/// [`Decorator::set`]: ../struct.Decorator.html#method.set
+#[no_mangle]
+pub unsafe extern "C" fn ornament_decorator_set(
+    decorator: *mut OrnamentDecorator,
+    face_id: u32,
+    start: usize,
+    end: usize,
+) -> bool {
+    let text = (*decorator).text();
+    if start > end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+        return false;
+    }
+    (*decorator).set(face_id, start..end);
+    true
+}
+
+/// Builds the decorated text. The decorator can still be used afterwards.
+///
+/// # Safety
+///
+/// `decorator` must be a valid decorator.
+#[no_mangle]
+pub unsafe extern "C" fn ornament_decorator_build(
+    decorator: *mut OrnamentDecorator,
+) -> *mut OrnamentText {
+    Box::into_raw(Box::new((*decorator).build()))
+}
+
+/// Releases a text. Does nothing if `text` is null.
+///
+/// # Safety
+///
+/// `text` must be null or returned by [`ornament_decorator_build`], and not released yet.
+///
+/// [`ornament_decorator_build`]: fn.ornament_decorator_build.html
+#[no_mangle]
+pub unsafe extern "C" fn ornament_text_free(text: *mut OrnamentText) {
+    if !text.is_null() {
+        drop(Box::from_raw(text));
+    }
+}
+
+/// Returns the number of fragments of a text.
+///
+/// # Safety
+///
+/// `text` must be a valid text.
+#[no_mangle]
+pub unsafe extern "C" fn ornament_text_fragment_count(text: *const OrnamentText) -> usize {
+    (*text).iter().len()
+}
+
+/// Writes the fragment at `index` to `fragment`. Returns `false`, leaving `fragment` untouched,
+/// if `index` is out of bounds. The fragment text is valid until the text is released.
+///
+/// # Safety
+///
+/// `text` must be a valid text and `fragment` must point to a writable `OrnamentFragment`.
+#[no_mangle]
+pub unsafe extern "C" fn ornament_text_fragment(
+    text: *const OrnamentText,
+    index: usize,
+    fragment: *mut OrnamentFragment,
+) -> bool {
+    match (*text).iter().nth(index) {
+        Some(tf) => {
+            ptr::write(
+                fragment,
+                OrnamentFragment {
+                    ptr: tf.text.as_ptr(),
+                    len: tf.text.len(),
+                    face_id: tf.face,
+                },
+            );
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,160 @@
+use crate::Buffer;
+use std::borrow::Cow;
+use std::cmp::max;
+use std::ops::Range;
+use std::str;
+
+/// A gap buffer, a [`Buffer`] for edit-heavy workloads such as prompts or form fields.
+///
+/// The free capacity is kept at the position of the last edit, so that repeated edits around the
+/// same position only move the bytes between two consecutive edits, instead of the whole tail of
+/// the text.
+///
+/// Only the text storage benefits from it: an edit through a [`Decorator`] still rebuilds the
+/// decorations, in time linear in the number of decorated fragments. Batching the edits given
+/// to [`apply_edits`] keeps this cost to once per batch.
+///
+/// ```
+/// use ornament::{Decorator, GapBuffer};
+///
+/// let mut decorator = Decorator::with_buffer(GapBuffer::from("fn main() {}"));
+/// decorator.set("keyword", 0..2);
+/// for (i, c) in "foo".chars().enumerate() {
+///     decorator.apply_edits(&[(3 + i..3 + i, c.to_string())]);
+/// }
+/// let text = decorator.build();
+/// assert_eq!(text.plain(), "fn foomain() {}");
+/// assert_eq!(text.face_at(0), Some(&"keyword"));
+/// ```
+///
+/// [`Buffer`]: trait.Buffer.html
+/// [`Decorator`]: struct.Decorator.html
+/// [`apply_edits`]: struct.Decorator.html#method.apply_edits
+#[derive(Clone, Debug, Default)]
+pub struct GapBuffer {
+    bytes: Vec<u8>,
+    gap: Range<usize>,
+}
+
+impl GapBuffer {
+    /// Creates a new empty `GapBuffer`.
+    pub fn new() -> GapBuffer {
+        GapBuffer::default()
+    }
+
+    /// Moves the gap so that it starts at `offset`.
+    fn move_gap(&mut self, offset: usize) {
+        let Range { start, end } = self.gap.clone();
+        if offset < start {
+            let moved = start - offset;
+            self.bytes.copy_within(offset..start, end - moved);
+            self.gap = offset..end - moved;
+        } else if offset > start {
+            let moved = offset - start;
+            self.bytes.copy_within(end..end + moved, start);
+            self.gap = offset..end + moved;
+        }
+    }
+
+    /// Grows the gap so that it can hold at least `len` bytes.
+    fn reserve(&mut self, len: usize) {
+        if self.gap.len() >= len {
+            return;
+        }
+        let extra = max(len - self.gap.len(), max(self.bytes.len(), 16));
+        self.bytes
+            .splice(self.gap.end..self.gap.end, vec![0; extra]);
+        self.gap.end += extra;
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        if offset == self.len() {
+            return true;
+        }
+        let idx = if offset < self.gap.start {
+            offset
+        } else {
+            offset + self.gap.len()
+        };
+        // Continuation bytes are `0b10xx_xxxx`.
+        self.bytes[idx] & 0xc0 != 0x80
+    }
+
+    fn as_str(bytes: &[u8]) -> &str {
+        str::from_utf8(bytes).expect("gap inside a char")
+    }
+}
+
+impl From<&str> for GapBuffer {
+    fn from(s: &str) -> GapBuffer {
+        let mut buffer = GapBuffer::new();
+        buffer.push_str(s);
+        buffer
+    }
+}
+
+impl Buffer for GapBuffer {
+    fn len(&self) -> usize {
+        self.bytes.len() - self.gap.len()
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.replace_range(self.len()..self.len(), text);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a bound of the range is out of bounds or not on a `char` boundary.
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "range out of bounds"
+        );
+        assert!(
+            self.is_char_boundary(range.start) && self.is_char_boundary(range.end),
+            "range not on char boundaries"
+        );
+        self.move_gap(range.start);
+        self.gap.end += range.len();
+        self.reserve(text.len());
+        let start = self.gap.start;
+        self.bytes[start..start + text.len()].copy_from_slice(text.as_bytes());
+        self.gap.start += text.len();
+    }
+
+    /// Edits in place, moving the gap from an edit to the previous one.
+    fn replace_ranges(&mut self, edits: &[(Range<usize>, &str)]) {
+        for (range, text) in edits.iter().rev() {
+            self.replace_range(range.clone(), text);
+        }
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        let Range { start, end } = self.gap.clone();
+        if range.end <= start {
+            Cow::Borrowed(GapBuffer::as_str(&self.bytes[range]))
+        } else if range.start >= start {
+            let shift = end - start;
+            Cow::Borrowed(GapBuffer::as_str(
+                &self.bytes[range.start + shift..range.end + shift],
+            ))
+        } else {
+            let mut s = String::with_capacity(range.len());
+            s.push_str(GapBuffer::as_str(&self.bytes[range.start..start]));
+            s.push_str(GapBuffer::as_str(&self.bytes[end..range.end + end - start]));
+            Cow::Owned(s)
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        let len = self.len();
+        self.move_gap(len);
+        self.bytes.truncate(len);
+        self.bytes.shrink_to_fit();
+        self.gap = len..len;
+    }
+}
@@ -0,0 +1,37 @@
+use crate::Text;
+
+impl<F> Text<F> {
+    /// Converts the decorated text into its plain text and a Pango attribute list, ready to be
+    /// given to a GTK label or a Pango layout. `attributes` returns the Pango attributes of a face,
+    /// their indexes are set by this method.
+    ///
+    /// ```no_run
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("An important word.").set(true, 3..12).build();
+    /// let (plain, attrs) = text.to_pango(|&strong| {
+    ///     if strong {
+    ///         vec![pango::AttrInt::new_weight(pango::Weight::Bold).into()]
+    ///     } else {
+    ///         Vec::new()
+    ///     }
+    /// });
+    /// ```
+    pub fn to_pango<A>(&self, attributes: A) -> (String, pango::AttrList)
+    where
+        A: Fn(&F) -> Vec<pango::Attribute>,
+    {
+        let list = pango::AttrList::new();
+        let mut start = 0;
+        for tf in self {
+            let end = start + tf.text.len();
+            for mut attr in attributes(&tf.face) {
+                attr.set_start_index(start as u32);
+                attr.set_end_index(end as u32);
+                list.insert(attr);
+            }
+            start = end;
+        }
+        (self.plain(), list)
+    }
+}
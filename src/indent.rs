@@ -0,0 +1,72 @@
+use crate::{Buffer, Decorator};
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Appends `text` with each of its lines prefixed by `prefix`, both with the current face. A
+    /// final line break doesn't produce a prefixed empty line.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::new()
+    ///     .append("Quote:\n")
+    ///     .set_face("quote")
+    ///     .append_indented("first\n\nlast\n", "> ")
+    ///     .build();
+    /// assert_eq!(text.plain(), "Quote:\n> first\n> \n> last\n");
+    /// assert_eq!(text.face_at(7), Some(&"quote"));
+    /// ```
+    pub fn append_indented(&mut self, text: &str, prefix: &str) -> &mut Self {
+        for line in text.split_inclusive('\n') {
+            self.append(prefix).append(line);
+        }
+        self
+    }
+
+    /// Appends `text` with the leading whitespace common to all its non-blank lines removed, with
+    /// the current face. Tabs and spaces are not considered equal. Blank lines are appended empty.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::<()>::new()
+    ///     .append_dedented("    fn main() {\n        run();\n  \n    }\n")
+    ///     .build();
+    /// assert_eq!(text.plain(), "fn main() {\n    run();\n\n}\n");
+    /// ```
+    pub fn append_dedented(&mut self, text: &str) -> &mut Self {
+        let is_blank = |line: &str| line.trim().is_empty();
+        let mut margin: Option<&str> = None;
+        for line in text.lines().filter(|line| !is_blank(line)) {
+            let leading = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+            margin = Some(match margin {
+                Some(margin) => {
+                    let common = margin
+                        .bytes()
+                        .zip(leading.bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    &margin[..common]
+                }
+                None => leading,
+            });
+        }
+        let indent = margin.map_or(0, str::len);
+        for line in text.split_inclusive('\n') {
+            if is_blank(line) {
+                let ending = line.len() - line.trim_end_matches(['\r', '\n']).len();
+                self.append(&line[line.len() - ending..]);
+            } else {
+                self.append(&line[indent..]);
+            }
+        }
+        self
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{Text, TextFragment};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Index;
+
+/// A small handle to a face stored in a [`FaceInterner`].
+///
+/// The default value is the handle of the default face of the interner.
+///
+/// [`FaceInterner`]: struct.FaceInterner.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct FaceId(u32);
+
+impl FaceId {
+    /// Returns the index of the face in its interner.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A table of faces handing out a [`FaceId`] for each distinct face.
+///
+/// Using `FaceId`s instead of the faces themselves in a [`Text`] makes cloning and comparing
+/// faces cheap when they are costly values, e.g. strings of highlight groups.
+///
+/// ```
+/// use ornament::{Decorator, FaceInterner};
+///
+/// let mut interner = FaceInterner::new();
+/// let keyword = interner.intern("keyword".to_string());
+/// let text = Decorator::with_text("let x = 1;")
+///     .set(keyword, 0..3)
+///     .build();
+/// assert_eq!(interner[keyword], "keyword");
+/// assert_eq!(text.resolve(&interner).face_at(0), Some(&"keyword".to_string()));
+/// assert_eq!(text.resolve(&interner).face_at(4), Some(&String::new()));
+/// ```
+///
+/// [`FaceId`]: struct.FaceId.html
+/// [`Text`]: struct.Text.html
+#[derive(Clone, Debug)]
+pub struct FaceInterner<F> {
+    faces: Vec<F>,
+    ids: HashMap<F, FaceId>,
+}
+
+impl<F> FaceInterner<F>
+where
+    F: Clone + Default + Eq + Hash,
+{
+    /// Creates a new `FaceInterner` containing only the default face.
+    pub fn new() -> FaceInterner<F> {
+        let mut interner = FaceInterner {
+            faces: Vec::new(),
+            ids: HashMap::new(),
+        };
+        interner.intern(F::default());
+        interner
+    }
+
+    /// Returns the handle of `face`, adding it to the table if needed.
+    pub fn intern(&mut self, face: F) -> FaceId {
+        if let Some(id) = self.ids.get(&face) {
+            return *id;
+        }
+        let id = FaceId(self.faces.len() as u32);
+        self.faces.push(face.clone());
+        self.ids.insert(face, id);
+        id
+    }
+
+    /// Returns the handle of `face` if it is in the table.
+    pub fn id(&self, face: &F) -> Option<FaceId> {
+        self.ids.get(face).copied()
+    }
+
+    /// Returns the face of the handle `id`, or `None` if it doesn't come from this table.
+    pub fn get(&self, id: FaceId) -> Option<&F> {
+        self.faces.get(id.index())
+    }
+
+    /// Returns the number of faces interned in the table, not counting the default face.
+    ///
+    /// ```
+    /// use ornament::FaceInterner;
+    ///
+    /// let mut interner = FaceInterner::new();
+    /// assert!(interner.is_empty());
+    /// interner.intern("keyword");
+    /// interner.intern("");
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.faces.len() - 1
+    }
+
+    /// Returns `true` if no face other than the default one was interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<F> Default for FaceInterner<F>
+where
+    F: Clone + Default + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> Index<FaceId> for FaceInterner<F> {
+    type Output = F;
+
+    fn index(&self, id: FaceId) -> &F {
+        &self.faces[id.index()]
+    }
+}
+
+impl<F> Text<F>
+where
+    F: Clone + Default + Eq + Hash,
+{
+    /// Returns a copy of the text with the faces replaced by their handle in `interner`.
+    pub fn intern(&self, interner: &mut FaceInterner<F>) -> Text<FaceId> {
+        self.iter()
+            .map(|tf| TextFragment {
+                text: tf.text.clone(),
+                face: interner.intern(tf.face.clone()),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl Text<FaceId> {
+    /// Returns a copy of the text with the handles replaced by their face in `interner`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a handle doesn't come from `interner`.
+    pub fn resolve<F: Clone>(&self, interner: &FaceInterner<F>) -> Text<F> {
+        self.iter()
+            .map(|tf| TextFragment {
+                text: tf.text.clone(),
+                face: interner[tf.face].clone(),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
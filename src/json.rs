@@ -0,0 +1,88 @@
+use crate::{Text, TextFragment};
+use serde::de::Error;
+use serde_json::{Map, Value};
+
+impl<F> Text<F> {
+    /// Converts the text into a JSON value, `face_to_value` converting each face.
+    ///
+    /// The value has the same shape as the serialized `Text`: an array of objects with `text`
+    /// and `face` fields. This allows exporting faces which don't implement `Serialize`.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Text};
+    /// use serde_json::json;
+    ///
+    /// // A style without `Serialize`.
+    /// #[derive(Clone, Debug, Default, PartialEq)]
+    /// struct Style {
+    ///     weight: u16,
+    /// }
+    ///
+    /// let text = Decorator::new()
+    ///     .append("a ")
+    ///     .set_face(Style { weight: 700 })
+    ///     .append("word")
+    ///     .build();
+    /// let value = text.to_json_value(|style| json!(style.weight));
+    /// assert_eq!(
+    ///     value,
+    ///     json!([{"text": "a ", "face": 0}, {"text": "word", "face": 700}])
+    /// );
+    ///
+    /// let back = Text::from_json_value(value, |face| {
+    ///     serde_json::from_value(face).map(|weight| Style { weight })
+    /// })
+    /// .unwrap();
+    /// assert_eq!(back, text);
+    /// ```
+    pub fn to_json_value<V>(&self, face_to_value: V) -> Value
+    where
+        V: Fn(&F) -> Value,
+    {
+        Value::Array(
+            self.iter()
+                .map(|tf| {
+                    let mut object = Map::new();
+                    object.insert("text".to_owned(), Value::String(tf.text.clone()));
+                    object.insert("face".to_owned(), face_to_value(&tf.face));
+                    Value::Object(object)
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a text from a JSON value produced by [`to_json_value`], `value_to_face`
+    /// converting each face back.
+    ///
+    /// [`to_json_value`]: struct.Text.html#method.to_json_value
+    pub fn from_json_value<V>(value: Value, value_to_face: V) -> serde_json::Result<Text<F>>
+    where
+        V: Fn(Value) -> serde_json::Result<F>,
+    {
+        let fragments = match value {
+            Value::Array(fragments) => fragments,
+            _ => return Err(serde_json::Error::custom("expected an array of fragments")),
+        };
+        fragments
+            .into_iter()
+            .map(|fragment| {
+                let mut object = match fragment {
+                    Value::Object(object) => object,
+                    _ => return Err(serde_json::Error::custom("expected a fragment object")),
+                };
+                let text = match object.remove("text") {
+                    Some(Value::String(text)) => text,
+                    _ => return Err(serde_json::Error::missing_field("text")),
+                };
+                let face = object
+                    .remove("face")
+                    .ok_or_else(|| serde_json::Error::missing_field("face"))?;
+                Ok(TextFragment {
+                    text,
+                    face: value_to_face(face)?,
+                })
+            })
+            .collect::<serde_json::Result<Vec<_>>>()
+            .map(Text::from)
+    }
+}
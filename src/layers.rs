@@ -0,0 +1,151 @@
+use crate::{bounded, Spans, Text, TextFragment};
+use std::cmp::min;
+use std::ops::{Range, RangeBounds};
+
+/// A decorated text with additional named decoration layers on top of it.
+///
+/// The layers (search matches, selection, diagnostics...) are kept apart from the base text and
+/// only combined with it by [`resolve`], so that a transient layer can be changed without building
+/// the base text again. Layers are stacked in creation order, the most recent one on top.
+///
+/// ```
+/// use ornament::{Decorator, LayeredText};
+///
+/// let base = Decorator::with_text("let x = 1;").set("keyword", 0..3).build();
+/// let mut layered = LayeredText::from(base);
+/// layered.set("selection", "selected", 2..5);
+///
+/// let resolve = |base: &&str, layers: &[(&str, &&str)]| match layers.last() {
+///     Some((_, face)) => format!("{}+{}", base, face),
+///     None => base.to_string(),
+/// };
+/// let text = layered.resolve(resolve);
+/// assert_eq!(
+///     text.iter().map(|tf| tf.face.as_str()).collect::<Vec<_>>(),
+///     vec!["keyword", "keyword+selected", "+selected", ""]
+/// );
+///
+/// layered.clear("selection");
+/// assert_eq!(layered.resolve(resolve).text_len(), 10);
+/// ```
+///
+/// [`resolve`]: struct.LayeredText.html#method.resolve
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayeredText<F> {
+    base: Text<F>,
+    layers: Vec<Layer<F>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Layer<F> {
+    name: String,
+    ranges: Vec<(F, Range<usize>)>,
+}
+
+impl<F> LayeredText<F> {
+    /// Returns the base text.
+    pub fn base(&self) -> &Text<F> {
+        &self.base
+    }
+
+    /// Returns the names of the layers, from bottom to top.
+    pub fn layers(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|layer| layer.name.as_str())
+    }
+
+    /// Assigns `face` to the given range in `layer`. The layer is created on top of the others if
+    /// it doesn't exist. Inside a layer, the last assignation wins where ranges overlap.
+    ///
+    /// Any kind of range can be given, it is clamped to the length of the text. A range which
+    /// doesn't start or end on a `char` boundary is widened to the characters it overlaps.
+    pub fn set<R: RangeBounds<usize>>(&mut self, layer: &str, face: F, range: R) {
+        let mut range = bounded(range, self.base.text_len());
+        while !self.base.is_char_boundary(range.start) {
+            range.start -= 1;
+        }
+        while !self.base.is_char_boundary(range.end) {
+            range.end += 1;
+        }
+        if range.start >= range.end {
+            return;
+        }
+        match self.layers.iter_mut().find(|l| l.name == layer) {
+            Some(l) => l.ranges.push((face, range)),
+            None => self.layers.push(Layer {
+                name: layer.to_owned(),
+                ranges: vec![(face, range)],
+            }),
+        }
+    }
+
+    /// Removes all the decorations of `layer`, keeping its position in the stack.
+    pub fn clear(&mut self, layer: &str) {
+        if let Some(l) = self.layers.iter_mut().find(|l| l.name == layer) {
+            l.ranges.clear();
+        }
+    }
+
+    /// Removes `layer` from the stack.
+    pub fn remove_layer(&mut self, layer: &str) {
+        self.layers.retain(|l| l.name != layer);
+    }
+
+    /// Combines the base text with the layers and returns the resulting `Text`.
+    ///
+    /// `resolver` is given, for each piece of text, the base face and the faces of the layers
+    /// covering it (with their names, from bottom to top).
+    pub fn resolve<G, R>(&self, resolver: R) -> Text<G>
+    where
+        R: Fn(&F, &[(&str, &F)]) -> G,
+    {
+        // The runs of each layer, with the index of the range winning on each of them.
+        let len = self.base.text_len();
+        let runs: Vec<Vec<(Option<usize>, Range<usize>)>> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let mut spans = Spans::new();
+                spans.append(None, len);
+                for (idx, (_, range)) in layer.ranges.iter().enumerate() {
+                    spans.set(Some(idx), range.clone());
+                }
+                spans.flatten()
+            })
+            .collect();
+
+        let mut fragments = Vec::new();
+        let mut cursors = vec![0; runs.len()];
+        for (fragment, tf) in self.base.fragments_with_offsets() {
+            let mut start = fragment.start;
+            while start < fragment.end {
+                let mut end = fragment.end;
+                let mut active: Vec<(&str, &F)> = Vec::new();
+                for ((layer, runs), cursor) in self.layers.iter().zip(&runs).zip(&mut cursors) {
+                    while runs[*cursor].1.end <= start {
+                        *cursor += 1;
+                    }
+                    let (idx, run) = &runs[*cursor];
+                    end = min(end, run.end);
+                    if let Some(idx) = idx {
+                        active.push((layer.name.as_str(), &layer.ranges[*idx].0));
+                    }
+                }
+                fragments.push(TextFragment {
+                    text: tf.text[start - fragment.start..end - fragment.start].to_owned(),
+                    face: resolver(&tf.face, &active),
+                });
+                start = end;
+            }
+        }
+        fragments.into()
+    }
+}
+
+impl<F> From<Text<F>> for LayeredText<F> {
+    fn from(base: Text<F>) -> LayeredText<F> {
+        LayeredText {
+            base,
+            layers: Vec::new(),
+        }
+    }
+}
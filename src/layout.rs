@@ -0,0 +1,337 @@
+//! Layout helpers composing decorated texts into blocks, available with the `unicode-width`
+//! feature.
+
+use crate::table::truncated;
+use crate::{Decorator, Text};
+use std::cmp::min;
+use std::ops::{Bound, RangeBounds};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The characters drawing a box border.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Border {
+    pub top_left: char,
+    pub top: char,
+    pub top_right: char,
+    pub left: char,
+    pub right: char,
+    pub bottom_left: char,
+    pub bottom: char,
+    pub bottom_right: char,
+}
+
+impl Border {
+    /// `+-+` ASCII borders.
+    pub const ASCII: Border = Border::uniform('+', '-', '|');
+
+    /// `┌─┐` single line borders.
+    pub const SINGLE: Border = Border {
+        top_left: '┌',
+        top: '─',
+        top_right: '┐',
+        left: '│',
+        right: '│',
+        bottom_left: '└',
+        bottom: '─',
+        bottom_right: '┘',
+    };
+
+    /// `╭─╮` single line borders with rounded corners.
+    pub const ROUNDED: Border = Border {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..Border::SINGLE
+    };
+
+    /// `╔═╗` double line borders.
+    pub const DOUBLE: Border = Border {
+        top_left: '╔',
+        top: '═',
+        top_right: '╗',
+        left: '║',
+        right: '║',
+        bottom_left: '╚',
+        bottom: '═',
+        bottom_right: '╝',
+    };
+
+    /// Creates a border using the same character for the corners, the horizontal sides and the
+    /// vertical sides.
+    pub const fn uniform(corner: char, horizontal: char, vertical: char) -> Border {
+        Border {
+            top_left: corner,
+            top: horizontal,
+            top_right: corner,
+            left: vertical,
+            right: vertical,
+            bottom_left: corner,
+            bottom: horizontal,
+            bottom_right: corner,
+        }
+    }
+}
+
+/// Wraps the lines of `text` in a box drawn with `border` in `face`.
+///
+/// Lines are separated from the border by a space and padded to the display width of the widest
+/// one. The returned text ends with a line break.
+///
+/// ```
+/// use ornament::layout::{boxed, Border};
+/// use ornament::Decorator;
+///
+/// let text = Decorator::with_text("Note:\nwide 日本").set("bold", 0..5).build();
+/// let framed = boxed(&text, &Border::ROUNDED, "border");
+/// assert_eq!(
+///     framed.plain(),
+///     concat!(
+///         "╭───────────╮\n",
+///         "│ Note:     │\n",
+///         "│ wide 日本 │\n",
+///         "╰───────────╯\n",
+///     )
+/// );
+/// assert_eq!(framed.face_at(0), Some(&"border"));
+/// ```
+pub fn boxed<F>(text: &Text<F>, border: &Border, face: F) -> Text<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    let lines = text.lines();
+    let width = lines.iter().map(Text::width).max().unwrap_or(0);
+    let horizontal = |c: char| c.to_string().repeat(width + 2);
+
+    let mut decorator = Decorator::new();
+    decorator
+        .set_face(face.clone())
+        .append(&format!(
+            "{}{}{}\n",
+            border.top_left,
+            horizontal(border.top),
+            border.top_right
+        ))
+        .reset_face();
+    for line in &lines {
+        decorator
+            .set_face(face.clone())
+            .append(&border.left.to_string())
+            .reset_face()
+            .append(" ")
+            .append_text(line)
+            .reset_face()
+            .append(&" ".repeat(width - line.width() + 1))
+            .set_face(face.clone())
+            .append(&format!("{}\n", border.right))
+            .reset_face();
+    }
+    decorator
+        .set_face(face)
+        .append(&format!(
+            "{}{}{}\n",
+            border.bottom_left,
+            horizontal(border.bottom),
+            border.bottom_right
+        ))
+        .reset_face();
+    decorator.build()
+}
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Prefixes each line with its number, starting at `start`, followed by a `|` separator.
+    /// Numbers are right aligned and the gutter is decorated with `gutter_face`. Line breaks are
+    /// normalized to `\n`.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let source = "fn main() {\n".to_owned() + &"\n".repeat(8) + "}\n";
+    /// let text = Decorator::with_text(&source).set("keyword", 0..2).build();
+    /// let numbered = text.with_line_numbers(1, "gutter");
+    /// assert!(numbered.plain().starts_with(" 1 | fn main() {\n 2 | \n"));
+    /// assert!(numbered.plain().ends_with("10 | }\n"));
+    /// assert_eq!(numbered.face_at(0), Some(&"gutter"));
+    /// assert_eq!(numbered.face_at(5), Some(&"keyword"));
+    /// ```
+    pub fn with_line_numbers(&self, start: usize, gutter_face: F) -> Text<F> {
+        let lines = self.lines();
+        let width = (start + lines.len().saturating_sub(1)).to_string().len();
+        let mut decorator = Decorator::new();
+        for (idx, line) in lines.iter().enumerate() {
+            if idx > 0 {
+                decorator.append("\n");
+            }
+            decorator
+                .set_face(gutter_face.clone())
+                .append(&format!("{:>width$} | ", start + idx, width = width))
+                .reset_face()
+                .append_text(line);
+        }
+        if self.plain().ends_with('\n') {
+            decorator.append("\n");
+        }
+        decorator.build()
+    }
+
+    /// Decorates the leading `ratio` of the display width of `range` with `filled_face` and the
+    /// rest with `empty_face`, such as in a progress bar. `ratio` is clamped between 0 and 1 and
+    /// the filled width is rounded down.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let bar = Decorator::<&str>::with_text("[          ] 42%").build();
+    /// let bar = bar.fill_ratio(1..11, 0.42, "done", "todo");
+    /// assert_eq!(bar.face_at(4), Some(&"done"));
+    /// assert_eq!(bar.face_at(5), Some(&"todo"));
+    /// assert_eq!(bar.face_at(11), Some(&""));
+    /// ```
+    pub fn fill_ratio<R>(&self, range: R, ratio: f32, filled_face: F, empty_face: F) -> Text<F>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.text_len(),
+        };
+        let end = min(end, self.text_len());
+        if start >= end {
+            return self.clone();
+        }
+
+        let plain = self.plain();
+        let filled = (plain[start..end].width() as f32 * ratio.clamp(0.0, 1.0)) as usize;
+        let mut middle = start;
+        let mut used = 0;
+        for (idx, c) in plain[start..end].char_indices() {
+            used += c.width().unwrap_or(0);
+            if used > filled {
+                break;
+            }
+            middle = start + idx + c.len_utf8();
+        }
+
+        Decorator::from_text(self.clone())
+            .set(filled_face, start..middle)
+            .set(empty_face, middle..end)
+            .build()
+    }
+}
+
+/// Flows `text` into `count` side-by-side columns of `width` columns and `height` lines, as
+/// `ls -C` does, and returns the composed lines.
+///
+/// The text is wrapped to `width` and its lines fill the first column, then the next ones.
+/// Columns are separated by `gap` spaces. When the text doesn't fit in the columns, the
+/// remaining lines flow into another set of columns below.
+///
+/// ```
+/// use ornament::layout::flow_columns;
+/// use ornament::Decorator;
+///
+/// let text = Decorator::with_text("one two three four five").set(1, 0..3).build();
+/// let lines = flow_columns(&text, 2, 5, 2, 1);
+/// assert_eq!(
+///     lines.iter().map(|line| line.plain()).collect::<Vec<_>>(),
+///     vec!["one   three", "two   four", "five"]
+/// );
+/// assert_eq!(lines[0].face_at(0), Some(&1));
+/// ```
+pub fn flow_columns<F>(
+    text: &Text<F>,
+    count: usize,
+    width: usize,
+    height: usize,
+    gap: usize,
+) -> Vec<Text<F>>
+where
+    F: Clone + Default + PartialEq,
+{
+    let wrapped = text.wrap(width);
+    let height = height.max(1);
+    let per_block = height * count.max(1);
+    let mut lines = Vec::new();
+    for block in wrapped.chunks(per_block) {
+        let rows = min(height, block.len());
+        for row in 0..rows {
+            let mut decorator = Decorator::new();
+            let cells: Vec<&Text<F>> = block.iter().skip(row).step_by(height).collect();
+            for (idx, cell) in cells.iter().enumerate() {
+                if idx > 0 {
+                    decorator.append(&" ".repeat(gap));
+                }
+                decorator.append_text(cell);
+                if idx + 1 < cells.len() {
+                    decorator.append(&" ".repeat(width.saturating_sub(cell.width())));
+                }
+            }
+            lines.push(decorator.build());
+        }
+    }
+    lines
+}
+
+/// Lays out two texts side by side, such as the old and the new versions of a diff, and
+/// returns the composed lines.
+///
+/// Each pane is `width` columns wide: shorter lines are padded and longer ones truncated with an
+/// ellipsis. The panes are joined by `separator` decorated with `separator_face`. The shorter
+/// text is completed with empty lines.
+///
+/// ```
+/// use ornament::layout::side_by_side;
+/// use ornament::Decorator;
+///
+/// let old = Decorator::with_text("let a = 1;\nlet b = 2;").set("del", 11..21).build();
+/// let new = Decorator::with_text("let a = 1;").build();
+/// let lines = side_by_side(&old, &new, 6, " │ ", "sep");
+/// assert_eq!(
+///     lines.iter().map(|line| line.plain()).collect::<Vec<_>>(),
+///     vec!["let a… │ let a…", "let b… │       "]
+/// );
+/// assert_eq!(lines[1].face_at(0), Some(&"del"));
+/// ```
+pub fn side_by_side<F>(
+    left: &Text<F>,
+    right: &Text<F>,
+    width: usize,
+    separator: &str,
+    separator_face: F,
+) -> Vec<Text<F>>
+where
+    F: Clone + Default + PartialEq,
+{
+    let (left, right) = (left.lines(), right.lines());
+    let empty = Text::default();
+    let pane = |decorator: &mut Decorator<F>, line: &Text<F>| {
+        let (line, used) = if line.width() > width {
+            truncated(line, width)
+        } else {
+            (line.clone(), line.width())
+        };
+        decorator
+            .append_text(&line)
+            .reset_face()
+            .append(&" ".repeat(width - used));
+    };
+    (0..left.len().max(right.len()))
+        .map(|idx| {
+            let mut decorator = Decorator::new();
+            pane(&mut decorator, left.get(idx).unwrap_or(&empty));
+            decorator
+                .set_face(separator_face.clone())
+                .append(separator)
+                .reset_face();
+            pane(&mut decorator, right.get(idx).unwrap_or(&empty));
+            decorator.build()
+        })
+        .collect()
+}
@@ -60,18 +60,145 @@
 //! assert_eq!(another_other_text, text);
 //! ```
 
+mod annotations;
+#[cfg(feature = "attrs")]
+pub mod ansi;
+#[cfg(feature = "anstyle")]
+mod anstyle;
+#[cfg(feature = "attrs")]
+mod attrs;
+#[cfg(feature = "unicode-bidi")]
+mod bidi;
+mod buffer;
+pub mod chat;
+mod checkpoint;
+mod chunks;
+pub mod color;
+#[cfg(feature = "colored")]
+mod colored;
+mod compact;
+mod concat;
+mod cursor;
 mod decorations;
+#[cfg(feature = "diff")]
+pub mod diff;
+mod edit;
+mod face;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod gap;
+#[cfg(feature = "gtk")]
+mod gtk;
+mod indent;
+mod intern;
+#[cfg(feature = "json")]
+mod json;
+mod layers;
+#[cfg(feature = "unicode-width")]
+pub mod layout;
+mod links;
+#[cfg(feature = "unicode-width")]
+mod lists;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod logging;
+mod memory;
+mod meta;
+#[cfg(feature = "unicode-normalization")]
+mod normalization;
+mod owned;
+#[cfg(feature = "owo-colors")]
+mod owo;
+mod sanitize;
+mod search;
+#[cfg(feature = "unicode-segmentation")]
+mod segmentation;
+pub mod snippet;
+mod spans;
+mod static_text;
+mod stream;
+#[cfg(feature = "unicode-width")]
+pub mod table;
+#[cfg(feature = "termwiz")]
+mod termwiz;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod text;
+mod theme;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "unicode-width")]
+mod wrap;
 
-use std::cmp::{max, min};
-use std::ops::Range;
+use std::cmp::min;
+use std::ops::{Bound, Range, RangeBounds};
+use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "serde_support")]
 #[macro_use]
 extern crate serde;
 
+use annotations::Annotation;
+#[cfg(feature = "attrs")]
+pub use attrs::{AttrFlags, Attrs};
+pub use buffer::Buffer;
+pub use checkpoint::Checkpoint;
+use checkpoint::Pending;
+#[cfg(feature = "colored")]
+pub use colored::ColoredFace;
+use cursor::CursorSlot;
+pub use cursor::{Bias, Cursor};
 use decorations::Decorations;
-pub use text::{Text, TextFragment, TextIterator};
+pub use face::{FaceMerge, FaceResolve, FaceWrap};
+pub use gap::GapBuffer;
+pub use intern::{FaceId, FaceInterner};
+pub use layers::LayeredText;
+pub use memory::{DecorationStats, MemoryUsage};
+pub use meta::WithMeta;
+pub use owned::DecoratorOwned;
+#[cfg(feature = "owo-colors")]
+pub use owo::OwoFace;
+pub use spans::Spans;
+pub use static_text::StaticText;
+pub use stream::StreamRenderer;
+pub use text::{LastFragmentMut, Recovery, Text, TextFragment, TextIterator, Unmapped};
+pub use theme::Theme;
+
+/// Derives the rendering of a face enum.
+///
+/// Each variant can be given a prefix and a suffix with the `face` attribute, they default to
+/// an empty string. The derive generates `prefix()` and `suffix()` methods returning them and a
+/// `render` function wrapping a fragment text with them, ready to be given to [`Text::render`].
+/// [`FaceWrap`] is implemented too, so that [`Text::render_wrapped`] can be used instead.
+///
+/// ```
+/// use ornament::{Decorator, Face};
+///
+/// #[derive(Clone, Debug, Default, PartialEq, Face)]
+/// enum Markdown {
+///     #[default]
+///     Plain,
+///     #[face(prefix = "_", suffix = "_")]
+///     Emphasis,
+///     #[face(prefix = "**", suffix = "**")]
+///     Strong,
+/// }
+///
+/// let text = Decorator::with_text("Text can be with emphasis or even strong.")
+///     .set(Markdown::Emphasis, 17..25)
+///     .set(Markdown::Strong, 34..40)
+///     .build();
+/// assert_eq!(
+///     text.render(Markdown::render),
+///     "Text can be with _emphasis_ or even **strong**."
+/// );
+/// assert_eq!(text.render_wrapped(), text.render(Markdown::render));
+/// ```
+///
+/// [`Text::render`]: struct.Text.html#method.render
+/// [`FaceWrap`]: trait.FaceWrap.html
+/// [`Text::render_wrapped`]: struct.Text.html#method.render_wrapped
+#[cfg(feature = "derive")]
+pub use ornament_derive::Face;
 
 /// A helper type to build a [`Text`] instance.
 ///
@@ -80,13 +207,27 @@ pub use text::{Text, TextFragment, TextIterator};
 /// - Immediate by setting face ranges directly ([`set`] method).
 /// - A combination of both.
 ///
+/// The text is stored in a `String` by default, any other [`Buffer`] can be used instead (see
+/// [`with_buffer`]).
+///
 /// [`Text`]: struct.Text.html
 /// [`append`]: struct.Decorator.html#method.append
 /// [`set`]: struct.Decorator.html#method.set
-pub struct Decorator<F: Default> {
-    text: String,
+/// [`Buffer`]: trait.Buffer.html
+/// [`with_buffer`]: struct.Decorator.html#method.with_buffer
+pub struct Decorator<F: Default, B = String> {
+    text: B,
     current_face: F,
+    face_stack: Vec<F>,
     decorations: Decorations<F>,
+    priorities: Option<Decorations<u32>>,
+    flattened: Vec<(F, usize)>,
+    stale_from: usize,
+    cursors: Vec<Option<CursorSlot>>,
+    annotations: Vec<Annotation>,
+    revision: usize,
+    checkpoints: Vec<Arc<Mutex<Pending<F>>>>,
+    merge: Option<fn(&F, &F) -> F>,
 }
 
 impl<F> Decorator<F>
@@ -95,11 +236,7 @@ where
 {
     /// Creates a new empty `Decorator`.
     pub fn new() -> Decorator<F> {
-        Decorator {
-            text: String::new(),
-            current_face: F::default(),
-            decorations: Decorations::new(F::default()),
-        }
+        Decorator::with_buffer(String::new())
     }
 
     /// Creates a new `Decorator` initialized with `text`.
@@ -109,6 +246,91 @@ where
         decorator
     }
 
+    /// Creates a new `Decorator` initialized with a built `text`, keeping its faces. The
+    /// decorator can be used to edit the text and build it again.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Text};
+    ///
+    /// let text = Text::from_pairs(vec![("let", "keyword"), (" x = 1;", "")]);
+    /// let text = Decorator::from_text(text).set("var", 4..5).build();
+    /// assert_eq!(text.face_at(0), Some(&"keyword"));
+    /// assert_eq!(text.face_at(4), Some(&"var"));
+    /// ```
+    pub fn from_text(text: Text<F>) -> Decorator<F> {
+        let mut decorator = Decorator::new();
+        for (text, face) in text.into_pairs() {
+            decorator.append_fragment(TextFragment { text, face });
+        }
+        decorator
+    }
+
+    /// Returns the text appended so far.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut builder = Decorator::<()>::with_text("name");
+    /// if !builder.text().ends_with(':') {
+    ///     builder.append(":");
+    /// }
+    /// assert_eq!(builder.text(), "name:");
+    /// ```
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<F> From<Text<F>> for Decorator<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    fn from(text: Text<F>) -> Decorator<F> {
+        Decorator::from_text(text)
+    }
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Creates a new `Decorator` using `buffer` as text storage. The existing content of `buffer`
+    /// is assigned the default face.
+    ///
+    /// ```
+    /// # #[cfg(feature = "ropey")]
+    /// # {
+    /// use ornament::Decorator;
+    /// use ropey::Rope;
+    ///
+    /// let text = Decorator::<(), Rope>::with_buffer(Rope::from_str("A large document."))
+    ///     .append(" Even larger.")
+    ///     .build();
+    /// assert_eq!(text.plain(), "A large document. Even larger.");
+    /// # }
+    /// ```
+    pub fn with_buffer(buffer: B) -> Decorator<F, B> {
+        let mut decorations = Decorations::new(F::default());
+        if !buffer.is_empty() {
+            decorations.append(F::default(), buffer.len());
+        }
+        Decorator {
+            text: buffer,
+            current_face: F::default(),
+            face_stack: Vec::new(),
+            decorations,
+            priorities: None,
+            flattened: Vec::new(),
+            stale_from: 0,
+            cursors: Vec::new(),
+            annotations: Vec::new(),
+            revision: 0,
+            checkpoints: Vec::new(),
+            merge: None,
+        }
+    }
+
     /// Returns the current face. On init this value will be equivalent to `F::default()`.
     ///
     /// This face is mostly used by [`append`] which will assign the current face to the appended
@@ -119,6 +341,30 @@ where
         &self.current_face
     }
 
+    /// Returns the length of the text appended so far, in bytes.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Returns `true` if no text has been appended so far.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut builder = Decorator::new();
+    /// for item in ["a", "b"] {
+    ///     if !builder.is_empty() {
+    ///         builder.set_face("separator").append(", ");
+    ///     }
+    ///     builder.set_face("item").append(item);
+    /// }
+    /// assert_eq!(builder.len(), 4);
+    /// assert_eq!(builder.build().face_at(1), Some(&"separator"));
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
     /// Sets the current face.
     ///
     /// This method is chainable.
@@ -127,42 +373,680 @@ where
         self
     }
 
-    /// Resets the current face. This is equivalent to calling [`set_face`] with `F::default()`.
+    /// Resets the current face. This is equivalent to calling [`set_face`] with `F::default()`,
+    /// the faces saved by [`push_face`] are discarded too.
     ///
     /// This method is chainable.
     ///
     /// [`set_face`]: struct.Decorator.html#method.set_face
+    /// [`push_face`]: struct.Decorator.html#method.push_face
     pub fn reset_face(&mut self) -> &mut Self {
+        self.face_stack.clear();
         self.set_face(F::default());
         self
     }
 
+    /// Saves the current face on a stack and sets `face` as the current face. The saved face is
+    /// restored by [`pop_face`].
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::new()
+    ///     .push_face("list")
+    ///     .append("- ")
+    ///     .push_face("bold")
+    ///     .append("item")
+    ///     .pop_face()
+    ///     .append("\n")
+    ///     .pop_face()
+    ///     .append("end")
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&"list"));
+    /// assert_eq!(text.face_at(2), Some(&"bold"));
+    /// assert_eq!(text.face_at(6), Some(&"list"));
+    /// assert_eq!(text.face_at(7), Some(&""));
+    /// ```
+    ///
+    /// [`pop_face`]: struct.Decorator.html#method.pop_face
+    pub fn push_face(&mut self, face: F) -> &mut Self {
+        let previous = std::mem::replace(&mut self.current_face, face);
+        self.face_stack.push(previous);
+        self
+    }
+
+    /// Restores the face saved by the last call to [`push_face`] as the current face. The current
+    /// face is reset to `F::default()` if there is no saved face.
+    ///
+    /// This method is chainable.
+    ///
+    /// [`push_face`]: struct.Decorator.html#method.push_face
+    pub fn pop_face(&mut self) -> &mut Self {
+        self.current_face = self.face_stack.pop().unwrap_or_default();
+        self
+    }
+
+    /// Sets the current face to `face`, runs `f` and restores the previous current face.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::new()
+    ///     .with_face("quote", |d| {
+    ///         d.append("a ").with_face("bold", |d| {
+    ///             d.append("bold");
+    ///         });
+    ///         d.append(" quote");
+    ///     })
+    ///     .append(".")
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&"quote"));
+    /// assert_eq!(text.face_at(2), Some(&"bold"));
+    /// assert_eq!(text.face_at(6), Some(&"quote"));
+    /// assert_eq!(text.face_at(12), Some(&""));
+    /// ```
+    pub fn with_face<T>(&mut self, face: F, f: T) -> &mut Self
+    where
+        T: FnOnce(&mut Self),
+    {
+        let previous = std::mem::replace(&mut self.current_face, face);
+        f(self);
+        self.current_face = previous;
+        self
+    }
+
     /// Appends `text` to the buffer and assigns it the current face.
     ///
     /// This method is chainable.
     pub fn append(&mut self, text: &str) -> &mut Self {
-        self.text += text;
+        let len = self.text.len();
+        self.edited(len..len, text.len());
+        self.text.push_str(text);
         self.decorations
             .append(self.current_face.clone(), text.len());
+        if let Some(priorities) = &mut self.priorities {
+            priorities.append(0, text.len());
+        }
+        self
+    }
+
+    /// Appends the text of `fragment` with its face. The current face is left untouched.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::{Decorator, TextFragment};
+    ///
+    /// let fragment = TextFragment { text: "bold".to_string(), face: "bold" };
+    /// let mut decorator = Decorator::new();
+    /// decorator.set_face("dim").append("dim ").append_fragment(fragment);
+    /// assert_eq!(decorator.current_face(), &"dim");
+    /// let text = decorator.build();
+    /// assert_eq!(text.face_at(4), Some(&"bold"));
+    /// ```
+    pub fn append_fragment(&mut self, fragment: TextFragment<F>) -> &mut Self {
+        let face = std::mem::replace(&mut self.current_face, fragment.face);
+        self.append(&fragment.text);
+        self.current_face = face;
+        self
+    }
+
+    /// Appends every fragment of `text` with its face. The current face is left untouched.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let name = Decorator::with_text("name: value").set("key", 0..4).build();
+    /// let text = Decorator::with_text("{ ").append_text(&name).append(" }").build();
+    /// assert_eq!(text.plain(), "{ name: value }");
+    /// assert_eq!(text.face_at(2), Some(&"key"));
+    /// assert_eq!(text.face_at(6), Some(&""));
+    /// ```
+    pub fn append_text(&mut self, text: &Text<F>) -> &mut Self {
+        let face = self.current_face.clone();
+        for tf in text {
+            self.current_face = tf.face.clone();
+            self.append(&tf.text);
+        }
+        self.current_face = face;
+        self
+    }
+
+    /// Appends each string of `iter` with `face`. The current face is left untouched.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let tokens = vec!["fn", " ", "main"];
+    /// let text = Decorator::new()
+    ///     .extend_with_face(tokens, "code")
+    ///     .append(" is the entry point")
+    ///     .build();
+    /// assert_eq!(text.face_at(3), Some(&"code"));
+    /// assert_eq!(text.face_at(7), Some(&""));
+    /// ```
+    pub fn extend_with_face<I, S>(&mut self, iter: I, face: F) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let previous = std::mem::replace(&mut self.current_face, face);
+        for text in iter {
+            self.append(text.as_ref());
+        }
+        self.current_face = previous;
+        self
+    }
+
+    /// Assigns `face` to the given range. It overrides the faces previously assigned to this
+    /// range, or is layered on top of them in the combining mode (see [`combining`]), except the
+    /// ones assigned with a priority (see [`set_with_priority`]): `set` has the lowest priority.
+    ///
+    /// Any kind of range can be given, it is clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("dim: bold")
+    ///     .set("dim", ..)
+    ///     .set("bold", 5..)
+    ///     .set("label", ..=2)
+    ///     .build();
+    /// assert_eq!(text.face_at(2), Some(&"label"));
+    /// assert_eq!(text.face_at(3), Some(&"dim"));
+    /// assert_eq!(text.face_at(8), Some(&"bold"));
+    /// ```
+    ///
+    /// [`combining`]: struct.Decorator.html#method.combining
+    /// [`set_with_priority`]: struct.Decorator.html#method.set_with_priority
+    pub fn set<R: RangeBounds<usize>>(&mut self, face: F, range: R) -> &mut Self {
+        if let Some(safe_range) = self.safe_range(range) {
+            for sub_range in self.unprioritized(safe_range) {
+                match self.merge {
+                    Some(merge) => {
+                        for (old, sub_range) in self.decorations.faces_in(sub_range) {
+                            self.apply(merge(&old, &face), sub_range);
+                        }
+                    }
+                    None => self.apply(face.clone(), sub_range),
+                }
+            }
+        }
+        self
+    }
+
+    /// Switches to the combining mode: from now on, [`set`] and [`set_many`] layer their face on
+    /// top of the faces previously assigned using [`FaceMerge::merge`], as [`overlay`] does.
+    /// Faces assigned with a priority still replace the previous ones.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::{Decorator, FaceMerge};
+    ///
+    /// #[derive(Clone, Debug, Default, PartialEq)]
+    /// struct Style {
+    ///     bold: bool,
+    ///     italic: bool,
+    /// }
+    ///
+    /// impl FaceMerge for Style {
+    ///     fn merge(&self, other: &Self) -> Self {
+    ///         Style {
+    ///             bold: self.bold || other.bold,
+    ///             italic: self.italic || other.italic,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let bold = Style { bold: true, italic: false };
+    /// let italic = Style { bold: false, italic: true };
+    /// let text = Decorator::with_text("bold, both, plain")
+    ///     .combining()
+    ///     .set(bold.clone(), 0..10)
+    ///     .set(italic.clone(), 6..10)
+    ///     .replacing()
+    ///     .set(Style::default(), 12..)
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&bold));
+    /// assert_eq!(text.face_at(6), Some(&bold.merge(&italic)));
+    /// assert_eq!(text.face_at(12), Some(&Style::default()));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    /// [`set_many`]: struct.Decorator.html#method.set_many
+    /// [`overlay`]: struct.Decorator.html#method.overlay
+    /// [`FaceMerge::merge`]: trait.FaceMerge.html#tymethod.merge
+    pub fn combining(&mut self) -> &mut Self
+    where
+        F: FaceMerge,
+    {
+        self.merge = Some(F::merge);
+        self
+    }
+
+    /// Switches back to the default mode, where [`set`] and [`set_many`] override the faces
+    /// previously assigned.
+    ///
+    /// This method is chainable.
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    /// [`set_many`]: struct.Decorator.html#method.set_many
+    pub fn replacing(&mut self) -> &mut Self {
+        self.merge = None;
+        self
+    }
+
+    /// Assigns to the given range the faces returned by `f` for the faces previously assigned to
+    /// it. Like [`set`], the faces assigned with a priority are left untouched.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = y;")
+    ///     .set("keyword", 0..3)
+    ///     .set("var", 4..5)
+    ///     .set_with(0..5, |old| if old.is_empty() { "selected" } else { "selected-syntax" })
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&"selected-syntax"));
+    /// assert_eq!(text.face_at(3), Some(&"selected"));
+    /// assert_eq!(text.face_at(4), Some(&"selected-syntax"));
+    /// assert_eq!(text.face_at(5), Some(&""));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    pub fn set_with<R, T>(&mut self, range: R, mut f: T) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+        T: FnMut(&F) -> F,
+    {
+        if let Some(safe_range) = self.safe_range(range) {
+            for range in self.unprioritized(safe_range) {
+                for (old, sub_range) in self.decorations.faces_in(range) {
+                    self.apply(f(&old), sub_range);
+                }
+            }
+        }
+        self
+    }
+
+    /// Assigns each face to its range, as calling [`set`] for each of them in order would, but in
+    /// a single pass over the decorations. This is much faster for a large number of ranges, such
+    /// as the output of a syntax highlighter.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = y;")
+    ///     .set_many(vec![("keyword", 0..3), ("var", 4..5), ("var", 8..9), ("punct", 9..10)])
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&"keyword"));
+    /// assert_eq!(text.face_at(8), Some(&"var"));
+    /// assert_eq!(text.face_at(9), Some(&"punct"));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    pub fn set_many<I, R>(&mut self, ranges: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (F, R)>,
+        R: RangeBounds<usize>,
+    {
+        if self.merge.is_some() {
+            for (face, range) in ranges {
+                self.set(face, range);
+            }
+            return self;
+        }
+        let mut safe_ranges = Vec::new();
+        for (face, range) in ranges {
+            if let Some(safe_range) = self.safe_range(range) {
+                for sub_range in self.unprioritized(safe_range) {
+                    safe_ranges.push((face.clone(), sub_range));
+                }
+            }
+        }
+        let ranges = safe_ranges;
+        let start = match ranges.iter().map(|(_, range)| range.start).min() {
+            Some(start) => start,
+            None => return self,
+        };
+        self.preserve_checkpoints(start);
+        self.invalidate(start);
+        self.decorations = self.decorations.painted(&ranges);
+        self
+    }
+
+    /// Assigns `face` to the given range of `char`s, as [`set`] does with a byte range. The range
+    /// is clamped to the length of the text.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("née à Zürich").set_chars("city", 6..12).build();
+    /// assert_eq!(text.face_at(8), Some(&"city"));
+    /// assert_eq!(text.face_at(7), Some(&""));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    pub fn set_chars(&mut self, face: F, range: Range<usize>) -> &mut Self {
+        let len = self.text.len();
+        let text = self.text.slice(0..len);
+        let offset = |idx: usize| {
+            text.char_indices()
+                .nth(idx)
+                .map_or(len, |(offset, _)| offset)
+        };
+        let range = offset(range.start)..offset(range.end);
+        drop(text);
+        self.set(face, range)
+    }
+
+    /// Assigns `face` to the given range with a priority. Where ranges overlap, the face with the
+    /// highest priority wins whatever the call order, the last call wins between equal
+    /// priorities. Ranges assigned with [`set`] or [`append`] have the lowest priority: `0`.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = y;")
+    ///     .set_with_priority("error", 8..9, 10)
+    ///     .set_with_priority("keyword", 0..3, 1)
+    ///     .set_with_priority("variable", 4..5, 1)
+    ///     .set_with_priority("variable", 8..9, 1)
+    ///     .build();
+    /// assert_eq!(text.face_at(8), Some(&"error"));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    /// [`append`]: struct.Decorator.html#method.append
+    pub fn set_with_priority<R>(&mut self, face: F, range: R, priority: u32) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let safe_range = match self.safe_range(range) {
+            Some(safe_range) => safe_range,
+            None => return self,
+        };
+        self.preserve_checkpoints(safe_range.start);
+        let len = self.decorations.len();
+        let priorities = self.priorities.get_or_insert_with(|| {
+            let mut priorities = Decorations::new(0);
+            priorities.append(0, len);
+            priorities
+        });
+        let mut ranges = Vec::new();
+        for (old, sub_range) in priorities.faces_in(safe_range) {
+            if old <= priority {
+                priorities.set(priority, sub_range.clone());
+                ranges.push(sub_range);
+            }
+        }
+        for sub_range in ranges {
+            self.apply(face.clone(), sub_range);
+        }
         self
     }
 
-    /// Assigns `face` to the given range. It overrides all faces previously assigned to this range.
+    /// Layers `face` on top of all faces previously assigned to the given range. Unlike [`set`],
+    /// the existing faces are combined with `face` using [`FaceMerge::merge`] whatever the mode.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::{Decorator, FaceMerge};
+    ///
+    /// #[derive(Clone, Debug, Default, PartialEq)]
+    /// struct Style {
+    ///     bold: bool,
+    ///     italic: bool,
+    /// }
+    ///
+    /// impl FaceMerge for Style {
+    ///     fn merge(&self, other: &Self) -> Self {
+    ///         Style {
+    ///             bold: self.bold || other.bold,
+    ///             italic: self.italic || other.italic,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let bold = Style { bold: true, italic: false };
+    /// let italic = Style { bold: false, italic: true };
+    /// let text = Decorator::with_text("bold, both, italic")
+    ///     .set(bold.clone(), 0..10)
+    ///     .overlay(italic.clone(), 6..18)
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&bold));
+    /// assert_eq!(text.face_at(6), Some(&bold.merge(&italic)));
+    /// assert_eq!(text.face_at(12), Some(&italic));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    /// [`FaceMerge::merge`]: trait.FaceMerge.html#tymethod.merge
+    pub fn overlay<R>(&mut self, face: F, range: R) -> &mut Self
+    where
+        F: FaceMerge,
+        R: RangeBounds<usize>,
+    {
+        self.map_range(range, |old| old.merge(&face))
+    }
+
+    /// Replaces each face assigned to the given range by the face returned by `f` for it, the
+    /// fragments being split at the bounds of the range. Unlike [`set_with`], the faces
+    /// assigned with a priority are replaced too, their priorities are kept.
     ///
     /// This method is chainable.
-    pub fn set(&mut self, face: F, range: Range<usize>) -> &mut Self {
-        let safe_range = max(range.start, 0)..min(range.end, self.decorations.len());
-        self.decorations.set(face, safe_range);
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = y;")
+    ///     .set(1, 0..3)
+    ///     .set_with_priority(2, 4..5, 2)
+    ///     .map_range(2.., |old| old + 10)
+    ///     .set_with_priority(3, 4..9, 1)
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&1));
+    /// assert_eq!(text.face_at(2), Some(&11));
+    /// assert_eq!(text.face_at(4), Some(&12));
+    /// assert_eq!(text.face_at(5), Some(&3));
+    /// ```
+    ///
+    /// [`set_with`]: struct.Decorator.html#method.set_with
+    pub fn map_range<R, T>(&mut self, range: R, mut f: T) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+        T: FnMut(&F) -> F,
+    {
+        if let Some(safe_range) = self.safe_range(range) {
+            for (old, sub_range) in self.decorations.faces_in(safe_range) {
+                self.apply(f(&old), sub_range);
+            }
+        }
         self
     }
 
+    /// Clamps `range` to the buffer, returns `None` if the result is empty.
+    fn safe_range<R: RangeBounds<usize>>(&self, range: R) -> Option<Range<usize>> {
+        let safe_range = bounded(range, self.decorations.len());
+        if safe_range.start < safe_range.end {
+            Some(safe_range)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the parts of a valid and non-empty range which have the lowest priority.
+    fn unprioritized(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        match &self.priorities {
+            Some(priorities) => priorities
+                .faces_in(range)
+                .into_iter()
+                .filter(|(priority, _)| *priority == 0)
+                .map(|(_, sub_range)| sub_range)
+                .collect(),
+            None => vec![range],
+        }
+    }
+
+    /// Assigns `face` to a valid range whatever the priorities, and gives it the lowest priority.
+    /// Used for text which has just been inserted.
+    fn assign(&mut self, face: F, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        self.preserve_checkpoints(range.start);
+        if let Some(priorities) = &mut self.priorities {
+            priorities.set(0, range.clone());
+        }
+        self.apply(face, range);
+    }
+
+    /// Assigns `face` to a valid and non-empty range, leaving the priorities untouched.
+    fn apply(&mut self, face: F, range: Range<usize>) {
+        self.preserve_checkpoints(range.start);
+        self.invalidate(range.start);
+        self.decorations.set(face, range);
+    }
+
+    /// Marks the flattened decorations cache as outdated from `offset` onwards.
+    fn invalidate(&mut self, offset: usize) {
+        self.stale_from = min(self.stale_from, offset);
+    }
+
+    /// Records that `range` of the text is replaced by `inserted` bytes: invalidates the cache and
+    /// moves the positions tracked in the text.
+    fn edited(&mut self, range: Range<usize>, inserted: usize) {
+        self.invalidate(range.start);
+        for slot in self.cursors.iter_mut().flatten() {
+            slot.adjust(&range, inserted);
+        }
+        let mut annotations = Vec::with_capacity(self.annotations.len());
+        for annotation in std::mem::take(&mut self.annotations) {
+            annotation.adjust(&range, inserted, &mut annotations);
+        }
+        self.annotations = annotations;
+    }
+
+    /// Returns a report of the memory used by the buffer and the decoration structure.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            fragments: self.decorations.node_count(),
+            text_bytes: self.text.len(),
+            overhead: self.text.capacity() - self.text.len()
+                + self.decorations.allocated_bytes()
+                + self
+                    .priorities
+                    .as_ref()
+                    .map_or(0, Decorations::allocated_bytes)
+                + self.flattened.capacity() * std::mem::size_of::<(F, usize)>(),
+        }
+    }
+
+    /// Returns statistics about the decoration structure. Computing the number of fragments
+    /// requires flattening the whole structure.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut builder = Decorator::with_text("This error is important!");
+    /// builder.set("bold", 5..10).set("underline", 6..8);
+    /// let stats = builder.decoration_stats();
+    /// assert_eq!(stats.depth, 3);
+    /// assert_eq!(stats.fragments, 5);
+    /// ```
+    pub fn decoration_stats(&self) -> DecorationStats {
+        DecorationStats {
+            nodes: self.decorations.node_count(),
+            depth: self.decorations.depth(),
+            fragments: self.decorations.flatten_from(0).len(),
+        }
+    }
+
+    /// Removes all the text and its decorations, keeping the allocated capacity to reuse the
+    /// decorator. The current face is reset, the cursors are moved to the start of the text and
+    /// the annotations are dropped.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut builder = Decorator::new();
+    /// for record in ["started", "stopped"] {
+    ///     builder.clear().set_face("level").append("INFO ").reset_face().append(record);
+    ///     let line = builder.build();
+    ///     assert_eq!(line.plain(), format!("INFO {}", record));
+    ///     assert_eq!(line.face_at(0), Some(&"level"));
+    /// }
+    /// ```
+    pub fn clear(&mut self) -> &mut Self {
+        let len = self.text.len();
+        self.edited(0..len, 0);
+        self.revision += 1;
+        self.checkpoints.clear();
+        self.text.clear();
+        self.decorations.clear();
+        if let Some(priorities) = &mut self.priorities {
+            priorities.clear();
+        }
+        self.flattened.clear();
+        self.annotations.clear();
+        self.reset_face()
+    }
+
+    /// Shrinks the capacity of the buffer and the decoration structure as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.text.shrink_to_fit();
+        self.decorations.shrink_to_fit();
+        if let Some(priorities) = &mut self.priorities {
+            priorities.shrink_to_fit();
+        }
+        self.flattened.shrink_to_fit();
+    }
+
     /// Processes all face assignations and returns the corresponding `Text`.
+    ///
+    /// The processed assignations are kept between calls, only the parts modified since the
+    /// previous build are processed again.
     pub fn build(&mut self) -> Text<F> {
+        let mut kept = 0;
+        let mut offset = 0;
+        for (_, len) in &self.flattened {
+            if offset + len >= self.stale_from {
+                break;
+            }
+            kept += 1;
+            offset += len;
+        }
+        self.flattened.truncate(kept);
+        self.flattened
+            .append(&mut self.decorations.flatten_from(offset));
+        self.stale_from = usize::MAX;
+
         let mut fragments = Vec::new();
         let mut acc = 0;
-        for (face, len) in &self.decorations.flatten() {
+        for (face, len) in &self.flattened {
             fragments.push(TextFragment {
-                text: self.text[acc..acc + len].to_owned(),
+                text: self.text.slice(acc..acc + len).into_owned(),
                 face: face.clone(),
             });
             acc += len;
@@ -171,32 +1055,78 @@ where
     }
 }
 
-impl<F> Default for Decorator<F>
+impl<F, B> Default for Decorator<F, B>
 where
     F: Clone + Default + PartialEq,
+    B: Buffer,
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_buffer(B::default())
+    }
+}
+
+/// Appends each string with the current face.
+///
+/// ```
+/// use ornament::Decorator;
+///
+/// let mut decorator = Decorator::new();
+/// decorator.set_face("token");
+/// decorator.extend(vec!["let", " ", "x"]);
+/// decorator.extend(vec![String::from(";")]);
+/// assert_eq!(decorator.build().plain(), "let x;");
+/// ```
+impl<'a, F, B> Extend<&'a str> for Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for text in iter {
+            self.append(text);
+        }
     }
 }
 
+impl<F, B> Extend<String> for Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for text in iter {
+            self.append(&text);
+        }
+    }
+}
+
+/// Converts `range` to a `Range` whose end is clamped to `len`.
+pub(crate) fn bounded<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    start..min(end, len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[derive(Clone, Debug, PartialEq)]
+    #[derive(Clone, Debug, Default, PartialEq)]
     enum Face {
+        #[default]
         Default,
         Star,
         Pipe,
     }
 
-    impl Default for Face {
-        fn default() -> Self {
-            Face::Default
-        }
-    }
-
     fn decorator(tf: &TextFragment<Face>) -> String {
         use Face::*;
         match tf.face {
@@ -258,4 +1188,987 @@ mod tests {
         let text = Decorator::new().append(" ").set(Face::Star, 0..1).build();
         assert_eq!(text.render(decorator), "* *");
     }
+
+    #[test]
+    fn nested_set() {
+        let text = Decorator::with_text("ab")
+            .set_face(Face::Star)
+            .append("cdef")
+            .reset_face()
+            .set(Face::Pipe, 3..5)
+            .build();
+        assert_eq!(text.render(decorator), "ab*c*|de|*f*");
+    }
+
+    #[test]
+    fn empty_set() {
+        let text = Decorator::new()
+            .set(Face::Star, 0..0)
+            .append("ab")
+            .set(Face::Star, 1..1)
+            .set(Face::Star, 4..8)
+            .build();
+        assert_eq!(text.render(decorator), "ab");
+    }
+
+    #[test]
+    fn priorities() {
+        let text = Decorator::with_text("This error is important!")
+            .set_with_priority(Face::Pipe, 0..10, 2)
+            .set_with_priority(Face::Star, 5..24, 1)
+            .append(" Really.")
+            .set_with_priority(Face::Star, 25..31, 1)
+            .build();
+        assert_eq!(
+            text.render(decorator),
+            "|This error|* is important!* *Really*."
+        );
+
+        let text = Decorator::with_text("This error is important!")
+            .set_with_priority(Face::Pipe, 0..10, 2)
+            .set(Face::Star, 5..24)
+            .set_with_priority(Face::Pipe, 8..13, 1)
+            .build();
+        assert_eq!(text.render(decorator), "|This error|| is|* important!*");
+
+        let text = Decorator::with_text("This error")
+            .set_with_priority(Face::Pipe, 0..10, 2)
+            .set(Face::Star, 0..10)
+            .set_many(vec![(Face::Star, 0..4)])
+            .set_with(5..10, |_| Face::Star)
+            .build();
+        assert_eq!(text.render(decorator), "|This error|");
+
+        let text = Decorator::with_text("This error")
+            .set_with_priority(Face::Pipe, 0..10, 2)
+            .insert_with_face(4, "!", Face::Star)
+            .build();
+        assert_eq!(text.render(decorator), "|This|*!*| error|");
+    }
+
+    #[test]
+    fn metadata() {
+        let text = Decorator::<WithMeta<Face, u32>>::with_text("This error is important!")
+            .set(Face::Star.into(), 5..10)
+            .attach(1, 0..12)
+            .detach(0..2)
+            .build();
+        assert_eq!(
+            text.iter()
+                .map(|tf| (tf.text.as_str(), tf.face.meta))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Th", None),
+                ("is ", Some(1)),
+                ("error", Some(1)),
+                (" i", Some(1)),
+                ("s important!", None)
+            ]
+        );
+        assert_eq!(text.face_at(6).map(|f| &f.face), Some(&Face::Star));
+    }
+
+    #[test]
+    fn incremental_build() {
+        let mut builder = Decorator::with_text("This error");
+        assert_eq!(builder.build().render(decorator), "This error");
+        builder.set(Face::Star, 5..10);
+        assert_eq!(builder.build().render(decorator), "This *error*");
+        builder
+            .set_face(Face::Star)
+            .append("s")
+            .reset_face()
+            .append(" are");
+        assert_eq!(builder.build().render(decorator), "This *errors* are");
+        builder.set(Face::Pipe, 0..4).append(" important!");
+        assert_eq!(
+            builder.build().render(decorator),
+            "|This| *errors* are important!"
+        );
+        assert_eq!(
+            builder.build(),
+            Decorator::with_text("This errors are important!")
+                .set(Face::Star, 5..11)
+                .set(Face::Pipe, 0..4)
+                .build()
+        );
+    }
+
+    #[test]
+    fn position_queries() {
+        let text = Decorator::with_text("This error is important!")
+            .set(Face::Star, 5..10)
+            .build();
+        assert_eq!(text.locate(0), Some((0, 0)));
+        assert_eq!(text.locate(5), Some((1, 0)));
+        assert_eq!(text.locate(12), Some((2, 2)));
+        assert_eq!(text.locate(24), None);
+        assert_eq!(text.face_at(9), Some(&Face::Star));
+        assert_eq!(text.face_at(10), Some(&Face::Default));
+        assert_eq!(text.slice(2..7).render(decorator), "is *er*");
+        assert_eq!(text.slice(6..8).render(decorator), "*rr*");
+        assert_eq!(text.slice(20..30).render(decorator), "ant!");
+        assert_eq!(text.slice(8..8), Text::default());
+        assert_eq!(text.text_len(), 24);
+        assert_eq!(text.slice(2..7).text_len(), 5);
+        assert_eq!(Text::<Face>::default().text_len(), 0);
+    }
+
+    #[test]
+    fn color_conversions() {
+        use color::{Color, NamedColor};
+
+        for index in 16..=255 {
+            let color = Color::Ansi256(index);
+            assert_eq!(Color::from(color.to_rgb()).to_ansi256(), index);
+        }
+        assert_eq!(Color::Rgb(12, 12, 12).to_ansi256(), 232);
+        assert_eq!(Color::Rgb(250, 10, 5).to_named(), NamedColor::BrightRed);
+        assert_eq!(Color::Ansi256(4).to_named(), NamedColor::Blue);
+        assert_eq!(Color::Named(NamedColor::Cyan).to_ansi256(), 6);
+    }
+
+    #[test]
+    fn memory_usage() {
+        let mut builder = Decorator::with_text("This error is important!");
+        builder.set(Face::Star, 5..10);
+        let usage = builder.memory_usage();
+        assert_eq!(usage.fragments, 5);
+        assert_eq!(usage.text_bytes, 24);
+
+        let mut text = builder.build();
+        text.shrink_to_fit();
+        let usage = text.memory_usage();
+        assert_eq!(usage.fragments, 3);
+        assert_eq!(usage.text_bytes, 24);
+        assert_eq!(
+            usage.overhead,
+            3 * (std::mem::size_of::<TextFragment<Face>>() + std::mem::size_of::<usize>())
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    #[should_panic(expected = "\x1b[31m- two\n\x1b[0m\x1b[32m+ three\n\x1b[0m")]
+    fn assert_text_diff() {
+        assert_text_eq!("one\ntwo\n", "one\nthree\n");
+    }
+
+    #[test]
+    fn chat_rendering() {
+        use chat::{ChatStyle, Dialect};
+
+        let text = Decorator::new()
+            .set_face(Face::Star)
+            .append(" bold ")
+            .set_face(Face::Pipe)
+            .append("a`b")
+            .build();
+        let style = |face: &Face| match face {
+            Face::Star => ChatStyle::new().bold(),
+            Face::Pipe => ChatStyle::new().code(),
+            Face::Default => ChatStyle::new(),
+        };
+        assert_eq!(
+            text.render_chat(Dialect::Discord, style),
+            " **bold** `` a`b ``"
+        );
+        assert_eq!(
+            text.render_chat(Dialect::Slack, style),
+            " *bold* a\u{200b}`b"
+        );
+
+        let text = Decorator::with_text("a``b <c>")
+            .set(Face::Pipe, 0..8)
+            .build();
+        assert_eq!(text.render_chat(Dialect::Discord, style), "a\\`\\`b <c>");
+        let text = Decorator::with_text("<c>").set(Face::Pipe, 0..3).build();
+        assert_eq!(text.render_chat(Dialect::Slack, style), "`&lt;c&gt;`");
+    }
+
+    #[test]
+    fn telegram_entities() {
+        use chat::{EntityKind, MessageEntity};
+
+        let text = Decorator::new()
+            .set_face(Face::Star)
+            .append("é")
+            .set_face(Face::Pipe)
+            .append("𝄞x")
+            .build();
+        let (_, entities) = text.to_telegram(|face| match face {
+            Face::Star => vec![EntityKind::Bold],
+            Face::Pipe => vec![EntityKind::Bold, EntityKind::Code],
+            Face::Default => Vec::new(),
+        });
+        let entity = |kind, offset, length| MessageEntity {
+            kind,
+            offset,
+            length,
+        };
+        assert_eq!(
+            entities,
+            vec![
+                entity(EntityKind::Bold, 0, 4),
+                entity(EntityKind::Code, 1, 3)
+            ]
+        );
+
+        let text = Decorator::new()
+            .set_face(Face::Pipe)
+            .append("ab")
+            .set_face(Face::Star)
+            .append("c")
+            .reset_face()
+            .append("d")
+            .build();
+        let (_, entities) = text.to_telegram(|face| match face {
+            Face::Pipe => vec![EntityKind::Italic, EntityKind::Bold],
+            Face::Star | Face::Default => vec![EntityKind::Italic],
+        });
+        assert_eq!(
+            entities,
+            vec![
+                entity(EntityKind::Italic, 0, 4),
+                entity(EntityKind::Bold, 0, 2)
+            ]
+        );
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn table_layout() {
+        use table::{Alignment, Column, Table};
+
+        let cell = |face: Face, text: &str| Decorator::new().set_face(face).append(text).build();
+        let lines = Table::new(vec![Column::new().align(Alignment::Center).max_width(5)])
+            .row(vec![cell(Face::Star, "ab")])
+            .row(vec![Decorator::new()
+                .append("abcd")
+                .set_face(Face::Pipe)
+                .append("ef")
+                .build()])
+            .lines();
+        assert_eq!(lines[0].render(decorator), " *ab*  ");
+        assert_eq!(lines[1].render(decorator), "abcd…");
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn wrapping() {
+        let text = Decorator::with_text("  indented line\n\nabcdefgh 日本語")
+            .set(Face::Star, 2..10)
+            .build();
+        let lines: Vec<String> = text.wrap(5).iter().map(|l| l.render(decorator)).collect();
+        assert_eq!(
+            lines,
+            vec!["  *ind*", "*ented*", "line", "", "abcde", "fgh", "日本", "語"]
+        );
+    }
+
+    #[test]
+    fn batch_edits() {
+        let mut builder = Decorator::with_text("This error is important!");
+        builder
+            .set(Face::Star, 5..10)
+            .set_with_priority(Face::Pipe, 0..2, 2)
+            .annotate(5..10, "error");
+        let before = builder.cursor(5, Bias::Before);
+        let after = builder.cursor(5, Bias::After);
+        let inside = builder.cursor(9, Bias::Before);
+        builder.apply_edits(&[
+            (10..14, String::new()),
+            (5..5, "an ".to_owned()),
+            (6..8, "R".to_owned()),
+        ]);
+        builder.set_with_priority(Face::Star, 0..4, 1);
+        assert_eq!(
+            builder.build().render(decorator),
+            "|Th|*is* an *eRor*important!"
+        );
+        assert_eq!(builder.position(before), Some(5));
+        assert_eq!(builder.position(after), Some(8));
+        assert_eq!(builder.position(inside), Some(11));
+        assert_eq!(
+            builder.annotations::<&str>(),
+            vec![(8..9, &"error"), (10..12, &"error")]
+        );
+    }
+
+    #[test]
+    fn insertion_before_replacement() {
+        for edits in [
+            [(1..3, "X".to_owned()), (1..1, "Y".to_owned())],
+            [(1..1, "Y".to_owned()), (1..3, "X".to_owned())],
+        ] {
+            let mut builder = Decorator::<Face>::with_text("abcd");
+            builder.apply_edits(&edits);
+            assert_eq!(builder.build().plain(), "aYXd");
+
+            let mut builder = Decorator::<Face, _>::with_buffer(GapBuffer::from("abcd"));
+            builder.apply_edits(&edits);
+            assert_eq!(builder.build().plain(), "aYXd");
+        }
+    }
+
+    #[test]
+    fn highlight_across_fragments() {
+        let text = Decorator::new()
+            .append("This er")
+            .set_face(Face::Pipe)
+            .append("ror is an error")
+            .build()
+            .highlight_matches("error", Face::Star);
+        assert_eq!(text.render(decorator), "This *error*| is an |*error*");
+    }
+
+    #[test]
+    fn face_replacement() {
+        let mut text = Decorator::with_text("This error is important!")
+            .set(Face::Star, 5..10)
+            .set(Face::Pipe, 14..23)
+            .build();
+        text.replace_face(&Face::Star, Face::Pipe);
+        assert_eq!(text.render(decorator), "This |error| is |important|!");
+        text.strip_face(&Face::Pipe);
+        assert_eq!(text.iter().count(), 1);
+    }
+
+    #[test]
+    fn plain_comparison() {
+        let text = Decorator::new()
+            .append("This ")
+            .set_face(Face::Star)
+            .append("error")
+            .build();
+        assert_eq!(text, "This error");
+        assert_eq!(text, String::from("This error"));
+        assert_ne!(text, "This errors");
+        assert_ne!(text, "This_error");
+        assert_eq!(Text::<Face>::default(), "");
+    }
+
+    #[test]
+    fn compact_encoding() {
+        let text = Decorator::with_text("a ‹b› c")
+            .set("x:y".to_owned(), 2..9)
+            .build();
+        let compact = text.to_compact_string();
+        assert_eq!(compact, "a ‹b› c‹x:y:2..9›");
+        assert_eq!(Text::from_compact_str(&compact), Some(text));
+        assert_eq!(Text::<String>::from_compact_str("ab‹x:1..3›"), None);
+        assert_eq!(Text::<String>::from_compact_str("ab‹x:1-2›"), None);
+        assert_eq!(Text::<String>::from_compact_str("ab‹x:1..1›"), None);
+        assert_eq!(Text::<String>::from_compact_str("ab›"), None);
+    }
+
+    #[test]
+    fn stream_rendering() {
+        let text = Decorator::new()
+            .append("This ")
+            .set_face(Face::Star)
+            .append("weird ")
+            .set_face(Face::Pipe)
+            .append("tiny")
+            .reset_face()
+            .append(" error")
+            .build();
+        let delimiter = |face: &Face| match face {
+            Face::Default => String::new(),
+            Face::Star => "*".to_owned(),
+            Face::Pipe => "|".to_owned(),
+        };
+        let mut renderer = StreamRenderer::new(delimiter, delimiter);
+        let mut rendered = String::new();
+        for tf in &text {
+            for c in tf.text.chars() {
+                rendered += &renderer.push(tf.face.clone(), &c.to_string());
+            }
+        }
+        rendered += &renderer.finish();
+        assert_eq!(rendered, text.render(decorator));
+    }
+
+    #[test]
+    fn chunks_on_char_boundaries() {
+        let text = Decorator::with_text("a日本").set(Face::Star, 1..7).build();
+        let chunks: Vec<String> = text.chunks(2).iter().map(Text::plain).collect();
+        assert_eq!(chunks, vec!["a", "日", "本"]);
+        let chunks: Vec<String> = text.chunks(7).iter().map(Text::plain).collect();
+        assert_eq!(chunks, vec!["a日本"]);
+    }
+
+    #[test]
+    fn annotation_split() {
+        let mut builder = Decorator::<Face>::with_text("see crate::Text here");
+        builder
+            .annotate(4..15, "path")
+            .insert(4, "::")
+            .replace_range(11..13, "/")
+            .remove(13..14);
+        assert_eq!(builder.build().plain(), "see ::crate/Txt here");
+        assert_eq!(
+            builder.annotations::<&str>(),
+            vec![(6..11, &"path"), (12..15, &"path")]
+        );
+    }
+
+    #[test]
+    fn decorator_concatenation() {
+        let mut first = Decorator::with_text("This ");
+        first.set_with_priority(Face::Pipe, 0..4, 1);
+        let mut second = Decorator::with_text("error");
+        second.set(Face::Star, 0..5).annotate(0..5, "error");
+        let mut builder = first.concat(second);
+        assert_eq!(builder.build().render(decorator), "|This| *error*");
+        builder.set_with_priority(Face::Default, 0..10, 0);
+        assert_eq!(builder.build().render(decorator), "|This| error");
+        assert_eq!(builder.annotations::<&str>(), vec![(5..10, &"error")]);
+    }
+
+    #[test]
+    fn control_sanitization() {
+        let text = Decorator::with_text("a\u{9b}31mb\x1bPq\x1b\\c\x1b7d\re\x7f\u{85}\x1b]8;;x")
+            .set(Face::Star, 0..3)
+            .build();
+        assert_eq!(text.sanitize().render(decorator), "*a*bcde");
+    }
+
+    #[test]
+    fn dedented_append() {
+        let text = Decorator::new()
+            .set_face(Face::Star)
+            .append_dedented("\t  a\n\t b\r\n \n")
+            .reset_face()
+            .append_dedented("\t c\n d")
+            .build();
+        assert_eq!(text.render(decorator), "* a\nb\r\n\n*\t c\n d");
+    }
+
+    #[test]
+    fn insertion() {
+        let mut builder = Decorator::with_text("is important!");
+        builder.set(Face::Star, 0..2);
+        let end = builder.cursor(13, Bias::Before);
+        builder
+            .insert(0, "This ")
+            .insert_with_face(7, " error", Face::Pipe)
+            .insert(100, ".");
+        assert_eq!(
+            builder.build().render(decorator),
+            "This *is*| error| important!."
+        );
+        assert_eq!(builder.position(end), Some(24));
+    }
+
+    #[test]
+    fn removal() {
+        let mut builder = Decorator::with_text("This weird tiny error");
+        builder
+            .set(Face::Star, 5..10)
+            .set(Face::Pipe, 8..15)
+            .set_with_priority(Face::Star, 16..21, 1);
+        let cursor = builder.cursor(12, Bias::After);
+        builder.remove(7..12).remove(0..0).remove(20..30);
+        assert_eq!(builder.build().render(decorator), "This *we*|iny| *error*");
+        assert_eq!(builder.position(cursor), Some(7));
+        builder.set_with_priority(Face::Default, 0..16, 0);
+        assert_eq!(builder.build().render(decorator), "This weiny *error*");
+    }
+
+    #[test]
+    fn snippet_edges() {
+        use snippet::Snippet;
+
+        let text = Snippet::new("é\r\nb\n")
+            .label(1..2, Face::Star, "split")
+            .label(3..4, Face::Pipe, "line break")
+            .label(6..6, Face::Star, "end")
+            .build();
+        assert_eq!(
+            text.render(decorator),
+            concat!(
+                "1 | *é*\n",
+                "  | *^ split*\n",
+                "  |  |^ line break|\n",
+                "3 | \n",
+                "  | *^ end*\n",
+            )
+        );
+    }
+
+    #[test]
+    fn open_ranges() {
+        let mut builder = Decorator::with_text("> quoted text");
+        builder
+            .set(Face::Star, 2..)
+            .remove(..=1)
+            .replace_range_with_face(7.., "words", Face::Pipe)
+            .annotate(.., 1u8);
+        assert_eq!(builder.build().render(decorator), "*quoted *|words|");
+        assert_eq!(builder.annotations_at::<u8>(0), vec![(0..12, &1)]);
+        builder.remove_annotations::<u8, _>(11..);
+        assert!(builder.annotations::<u8>().is_empty());
+
+        let mut spans = Spans::new();
+        spans.append(Face::Default, 4).set(Face::Star, 2..);
+        assert_eq!(
+            spans.flatten(),
+            vec![(Face::Default, 0..2), (Face::Star, 2..4)]
+        );
+
+        let mut layered = LayeredText::from(Text::from("quoted"));
+        layered.set("selection", Face::Star, 3..);
+        assert_eq!(
+            layered.resolve(|_, layers| layers.len()).face_at(3),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn layered_text() {
+        let mut layered = LayeredText::from(Text::from("née à Zürich"));
+        layered.set("search", Face::Star, 2..3);
+        layered.set("selection", Face::Pipe, 0..5);
+        layered.set("search", Face::Pipe, 9..11);
+        layered.set("search", Face::Star, 10..);
+        let text = layered.resolve(|_, layers| {
+            layers
+                .iter()
+                .map(|(name, face)| format!("{}:{:?}", name, face))
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        assert_eq!(
+            text.iter()
+                .map(|tf| (tf.text.as_str(), tf.face.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("n", "selection:Pipe"),
+                ("é", "search:Star selection:Pipe"),
+                ("e ", "selection:Pipe"),
+                ("à Z", ""),
+                ("ürich", "search:Star"),
+            ]
+        );
+    }
+
+    #[test]
+    fn face_stack() {
+        let mut builder = Decorator::new();
+        builder
+            .push_face(Face::Star)
+            .push_face(Face::Pipe)
+            .append("a")
+            .reset_face()
+            .append("b")
+            .pop_face()
+            .append("c");
+        assert_eq!(builder.current_face(), &Face::Default);
+        builder
+            .set_face(Face::Pipe)
+            .push_face(Face::Star)
+            .append("d")
+            .pop_face()
+            .append("f");
+        assert_eq!(builder.build().render(decorator), "|a|bc*d*|f|");
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn list_face_stack() {
+        let mut builder = Decorator::new();
+        builder
+            .set_face(Face::Pipe)
+            .push_face(Face::Star)
+            .append("d");
+        builder.bullet_list(vec![Text::from("e")], Face::Default);
+        builder.pop_face().append("f");
+        assert_eq!(builder.build().render(decorator), "*d*• e\n|f|");
+    }
+
+    #[test]
+    fn bulk_decorations() {
+        let ranges = vec![
+            (Face::Star, 2..9),
+            (Face::Pipe, 0..4),
+            (Face::Default, 6..7),
+            (Face::Pipe, 12..30),
+            (Face::Star, 13..14),
+            (Face::Pipe, 5..5),
+        ];
+        let mut expected = Decorator::with_text("This error is important!");
+        expected.set(Face::Star, 18..20);
+        let mut builder = Decorator::with_text("This error is important!");
+        builder.set(Face::Star, 18..20);
+        for (face, range) in ranges.clone() {
+            expected.set(face, range);
+        }
+        builder.set_many(ranges);
+        let text = builder.build();
+        assert_eq!(text, expected.build());
+        assert_eq!(
+            text.render(decorator),
+            "|This|* e*r*ro*r i|s|* *|important!|"
+        );
+    }
+
+    #[test]
+    fn clear_keeps_capacity() {
+        let mut builder = Decorator::with_buffer(GapBuffer::from("This error"));
+        builder.set_with_priority(Face::Star, 5..10, 1);
+        let cursor = builder.cursor(7, Bias::After);
+        let capacity = builder.text.capacity();
+        builder.clear();
+        assert_eq!(builder.build().plain(), "");
+        assert_eq!(builder.position(cursor), Some(0));
+        assert_eq!(builder.text.capacity(), capacity);
+        builder.append("is").set(Face::Pipe, 0..1);
+        assert_eq!(builder.build().render(decorator), "|i|s");
+    }
+
+    #[test]
+    fn checkpoint_reverts() {
+        let mut builder = Decorator::with_text("This error");
+        builder.set(Face::Star, 5..10);
+        let cursor = builder.cursor(10, Bias::After);
+        let checkpoint = builder.checkpoint();
+        builder
+            .push_face(Face::Pipe)
+            .append(" is")
+            .set_with_priority(Face::Default, 0..12, 2);
+        builder.revert_to(checkpoint).append("!");
+        assert_eq!(builder.build().render(decorator), "This *error*!");
+        assert_eq!(builder.position(cursor), Some(11));
+        builder.set_with_priority(Face::Pipe, 0..4, 1);
+        assert_eq!(builder.build().render(decorator), "|This| *error*!");
+    }
+
+    #[test]
+    #[should_panic(expected = "text edited since the checkpoint")]
+    fn checkpoint_after_edit() {
+        let mut builder = Decorator::<Face>::with_text("This error");
+        let checkpoint = builder.checkpoint();
+        builder.remove(0..5).revert_to(checkpoint);
+    }
+
+    #[test]
+    fn dropped_checkpoint_after_edit() {
+        let mut builder = Decorator::<u8>::with_text("abcdef");
+        drop(builder.checkpoint());
+        builder.remove(0..5).set(1, 0..1);
+        assert_eq!(builder.build().face_at(0), Some(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint taken by another decorator")]
+    fn foreign_checkpoint() {
+        let mut other = Decorator::<Face>::with_text("ab");
+        let checkpoint = other.checkpoint();
+        let mut builder = Decorator::<Face>::with_text("ab");
+        builder.append("cd").revert_to(checkpoint);
+    }
+
+    #[test]
+    fn nested_checkpoints() {
+        let mut builder = Decorator::with_text("ab");
+        let outer = builder.checkpoint();
+        builder.set_face(Face::Star).append("c");
+        let inner = builder.checkpoint();
+        builder.append("d").set(Face::Pipe, 0..4);
+        let innermost = builder.checkpoint();
+        builder.revert_to(inner);
+        assert_eq!(builder.build().render(decorator), "ab*c*");
+        builder.append("e");
+        builder.revert_to(outer).append("f");
+        assert_eq!(builder.build().render(decorator), "abf");
+        drop(innermost);
+        assert_eq!(builder.checkpoints.len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "text edited since the checkpoint")]
+    fn checkpoint_after_revert() {
+        let mut builder = Decorator::with_text("ab");
+        let first = builder.checkpoint();
+        builder.set_face(Face::Star).append("c");
+        let second = builder.checkpoint();
+        builder.revert_to(first).append("xyz");
+        builder.revert_to(second);
+    }
+
+    #[test]
+    fn url_detection() {
+        let text = Decorator::with_text("(http://a.org/x_(y)), xhttp://no and https://")
+            .build()
+            .linkify(Face::Star);
+        assert_eq!(
+            text.render(decorator),
+            "(*http://a.org/x_(y)*), xhttp://no and https://"
+        );
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn normalization() {
+        let text = Decorator::new()
+            .set_face(Face::Star)
+            .append("e")
+            .set_face(Face::Pipe)
+            .append("\u{301}x")
+            .build();
+        assert_eq!(text.nfc().render(decorator), "*\u{e9}*|x|");
+        assert_eq!(text.nfc().nfd(), text.nfd());
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn grapheme_slicing() {
+        let text = Decorator::new()
+            .set_face(Face::Star)
+            .append("e")
+            .set_face(Face::Pipe)
+            .append("\u{301}👍🏽x")
+            .build();
+        assert_eq!(text.slice_graphemes(0..1).render(decorator), "*e*|\u{301}|");
+        assert_eq!(text.slice_graphemes(1..2).plain(), "👍🏽");
+        assert_eq!(text.slice_graphemes(2..10).plain(), "x");
+        assert_eq!(text.slice_graphemes(5..10).plain(), "");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_replacement() {
+        let text = Decorator::with_text("a=1, bb=22")
+            .set(Face::Pipe, 0..4)
+            .build()
+            .replace_regex(
+                &regex::Regex::new(r"(\w+)=(\d+)").unwrap(),
+                "$2:$1",
+                Some(Face::Star),
+            );
+        assert_eq!(text.render(decorator), "*1:a*|,| *22:bb*");
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn inline_diff() {
+        use diff::{diff, DiffFace, Granularity};
+
+        let text = diff("fn foo(a: u8)", "fn bar(a: u8, b: u8)", Granularity::Word);
+        let rendered = text.render(|tf| match tf.face {
+            DiffFace::Equal => tf.text.clone(),
+            DiffFace::Added => format!("{{+{}+}}", tf.text),
+            DiffFace::Removed => format!("[-{}-]", tf.text),
+        });
+        assert_eq!(rendered, "fn [-foo-]{+bar+}(a: u8{+, b: u8+})");
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn long_diff() {
+        use diff::{diff, DiffFace, Granularity};
+
+        let old: String = (0..20_000).map(|i| format!("{}\n", i)).collect();
+        let new = old.replace("\n1000\n", "\n").replace("\n15000\n", "\n-\n");
+        let text = diff(&old, &new, Granularity::Line);
+        let changed: Vec<_> = text
+            .iter()
+            .filter(|tf| tf.face != DiffFace::Equal)
+            .map(|tf| (tf.face, tf.text.as_str()))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                (DiffFace::Removed, "1000\n"),
+                (DiffFace::Removed, "15000\n"),
+                (DiffFace::Added, "-\n")
+            ]
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn ffi_fragments() {
+        use ffi::*;
+
+        let text = "let é = 1;";
+        let mut fragment = OrnamentFragment {
+            ptr: std::ptr::null(),
+            len: 0,
+            face_id: 0,
+        };
+        unsafe {
+            let decorator = ornament_decorator_new();
+            assert!(ornament_decorator_append(
+                decorator,
+                text.as_ptr(),
+                text.len(),
+                0
+            ));
+            assert!(!ornament_decorator_append(decorator, [0xff].as_ptr(), 1, 0));
+            assert!(ornament_decorator_set(decorator, 1, 0, 3));
+            assert!(!ornament_decorator_set(decorator, 1, 5, 6));
+            assert!(!ornament_decorator_set(decorator, 1, 3, 20));
+            assert!(!ornament_decorator_set(decorator, 1, 3, 2));
+            let built = ornament_decorator_build(decorator);
+            ornament_decorator_free(decorator);
+
+            assert_eq!(ornament_text_fragment_count(built), 2);
+            assert!(ornament_text_fragment(built, 1, &mut fragment));
+            let bytes = std::slice::from_raw_parts(fragment.ptr, fragment.len);
+            assert_eq!((bytes, fragment.face_id), (" é = 1;".as_bytes(), 0));
+            assert!(!ornament_text_fragment(built, 2, &mut fragment));
+            ornament_text_free(built);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_event() {
+        use logging::{event_text, LogFace, LogLevel};
+        use std::sync::Mutex;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        #[derive(Default)]
+        struct Capture(Mutex<Vec<Text<LogFace>>>);
+
+        impl tracing::Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                self.0.lock().unwrap().push(event_text(event));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let capture = std::sync::Arc::new(Capture::default());
+        tracing::subscriber::with_default(capture.clone(), || {
+            tracing::warn!(target: "app", user = "bob", retries = 3, "login failed");
+        });
+        let texts = capture.0.lock().unwrap();
+        assert_eq!(
+            texts[0].plain(),
+            "WARN app: login failed user=bob retries=3"
+        );
+        assert_eq!(texts[0].face_at(0), Some(&LogFace::Level(LogLevel::Warn)));
+        assert_eq!(texts[0].face_at(5), Some(&LogFace::Target));
+        assert_eq!(texts[0].face_at(10), Some(&LogFace::Message));
+        assert_eq!(texts[0].face_at(23), Some(&LogFace::FieldName));
+        assert_eq!(texts[0].face_at(28), Some(&LogFace::FieldValue));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn render_parallel() {
+        let mut builder = Decorator::new();
+        for i in 0..1000 {
+            builder
+                .set_face(if i % 2 == 0 { Face::Star } else { Face::Pipe })
+                .append(&i.to_string());
+        }
+        let text = builder.build();
+        assert_eq!(text.render_par(decorator), text.render(decorator));
+    }
+
+    #[test]
+    fn gap_buffer_edits() {
+        let mut buffer = GapBuffer::from("héllo wörld");
+        let mut expected = String::from("héllo wörld");
+        for (range, text) in [
+            (0..0, "¡"),
+            (7..9, "LL"),
+            (3..3, "ü"),
+            (15..15, "!"),
+            (5..8, ""),
+            (0..2, "ß"),
+        ] {
+            buffer.replace_range(range.clone(), text);
+            expected.replace_range(range, text);
+            assert_eq!(buffer.slice(0..buffer.len()), expected);
+            for (idx, _) in expected.char_indices() {
+                let halves = buffer.slice(0..idx) + buffer.slice(idx..expected.len());
+                assert_eq!(halves, expected);
+            }
+        }
+        buffer.shrink_to_fit();
+        assert_eq!(buffer.capacity(), expected.len());
+        assert_eq!(buffer.slice(0..buffer.len()), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "range not on char boundaries")]
+    fn gap_buffer_char_boundaries() {
+        GapBuffer::from("é").replace_range(0..1, "e");
+    }
+
+    #[cfg(feature = "ropey")]
+    #[test]
+    fn rope_buffer() {
+        let text = Decorator::with_buffer(ropey::Rope::from_str("This "))
+            .set_face(Face::Star)
+            .append("error")
+            .reset_face()
+            .append(" is important!")
+            .set(Face::Pipe, 0..4)
+            .build();
+        assert_eq!(text.render(decorator), "|This| *error* is important!");
+    }
+
+    #[cfg(all(feature = "anstyle", feature = "attrs"))]
+    #[test]
+    fn anstyle_conversions() {
+        use color::{Color, NamedColor};
+
+        for color in [
+            Color::Named(NamedColor::BrightCyan),
+            Color::Ansi256(208),
+            Color::Rgb(1, 2, 3),
+        ] {
+            assert_eq!(Color::from(::anstyle::Color::from(color)), color);
+        }
+        let style: ::anstyle::Style = Attrs::new().bold().reverse().fg(NamedColor::Red).into();
+        assert_eq!(
+            style,
+            ::anstyle::Style::new()
+                .bold()
+                .invert()
+                .fg_color(Some(::anstyle::AnsiColor::Red.into()))
+        );
+
+        let text = Decorator::with_text("warn: x").set(true, 0..4).build();
+        let rendered = text.render_ansi(color::ColorSupport::Ansi16, |&warning| {
+            if warning {
+                Attrs::new().bold().fg(Color::Rgb(255, 0, 0))
+            } else {
+                Attrs::new()
+            }
+        });
+        assert_eq!(rendered, "\x1b[1m\x1b[91mwarn\x1b[0m: x");
+    }
+
+    #[cfg(feature = "unicode-bidi")]
+    #[test]
+    fn visual_order() {
+        let text = Decorator::with_text("אב gd\nok")
+            .set(Face::Star, 0..2)
+            .set(Face::Pipe, 5..7)
+            .build();
+        let visual = text.to_visual_order(None);
+        assert_eq!(visual.render(decorator), "|gd| ב*א*\nok");
+
+        let text = Decorator::with_text("\u{5d0}\u{5b8}\u{5d1}")
+            .set(Face::Star, 2..4)
+            .build();
+        let visual = text.to_visual_order(None);
+        assert_eq!(visual.render(decorator), "\u{5d1}\u{5d0}*\u{5b8}*");
+    }
 }
@@ -0,0 +1,93 @@
+use crate::{Decorator, Text, WithMeta};
+use std::ops::Range;
+
+const SCHEMES: [&str; 3] = ["https://", "http://", "ftp://"];
+
+/// Returns the ranges of the URLs found in `text`.
+///
+/// A URL starts with a known scheme and stops before whitespace or a quote. Trailing punctuation
+/// and unbalanced closing parentheses are considered part of the surrounding prose.
+fn find_urls(text: &str) -> Vec<Range<usize>> {
+    let mut urls = Vec::new();
+    let mut pos = 0;
+    while let Some((start, scheme)) = SCHEMES
+        .iter()
+        .filter_map(|scheme| text[pos..].find(scheme).map(|idx| (pos + idx, scheme)))
+        .min_by_key(|&(start, _)| start)
+    {
+        let after_word = !text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        let mut end = text[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+            .map_or(text.len(), |idx| start + idx);
+        loop {
+            let url = &text[start..end];
+            match url.chars().next_back() {
+                Some('.' | ',' | ':' | ';' | '!' | '?' | '\'') => end -= 1,
+                Some(')') if url.matches('(').count() < url.matches(')').count() => end -= 1,
+                _ => break,
+            }
+        }
+        if after_word && end > start + scheme.len() {
+            urls.push(start..end);
+            pos = end;
+        } else {
+            pos = start + scheme.len();
+        }
+    }
+    urls
+}
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Returns the decorated text with the URLs it contains decorated with `face`.
+    ///
+    /// URLs start with `http://`, `https://` or `ftp://`. Trailing punctuation, such as the final
+    /// period of a sentence, is not considered part of a URL.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("Docs (see https://docs.rs/ornament).").build();
+    /// let linked = text.linkify("link");
+    /// assert_eq!(linked.face_at(10), Some(&"link"));
+    /// assert_eq!(linked.face_at(33), Some(&"link"));
+    /// assert_eq!(linked.face_at(34), Some(&""));
+    /// ```
+    pub fn linkify(&self, face: F) -> Text<F> {
+        let mut decorator = Decorator::from_text(self.clone());
+        for url in find_urls(&self.plain()) {
+            decorator.set(face.clone(), url);
+        }
+        decorator.build()
+    }
+}
+
+impl<F: Clone + Default + PartialEq> Text<WithMeta<F, String>> {
+    /// Returns the decorated text with each URL it contains attached to itself as metadata, the
+    /// faces being kept. URLs are detected as in [`linkify`].
+    ///
+    /// ```
+    /// use ornament::{Decorator, WithMeta};
+    ///
+    /// let text = Decorator::<WithMeta<(), String>>::with_text("Go to https://example.com.")
+    ///     .build()
+    ///     .attach_urls();
+    /// assert_eq!(
+    ///     text.face_at(6).and_then(|face| face.meta.as_deref()),
+    ///     Some("https://example.com")
+    /// );
+    /// assert_eq!(text.face_at(25).and_then(|face| face.meta.as_deref()), None);
+    /// ```
+    ///
+    /// [`linkify`]: struct.Text.html#method.linkify
+    pub fn attach_urls(&self) -> Text<WithMeta<F, String>> {
+        let plain = self.plain();
+        let mut decorator = Decorator::from_text(self.clone());
+        for url in find_urls(&plain) {
+            decorator.attach(plain[url.clone()].to_owned(), url);
+        }
+        decorator.build()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Buffer, Decorator, Text};
+use unicode_width::UnicodeWidthStr;
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Appends `items` as a bulleted list, each item on its own line after a bullet in
+    /// `bullet_face`. The continuation lines of an item, such as the ones produced by [`wrap`], are
+    /// indented to align with its first line. The current face is left untouched.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let items = vec![
+    ///     Decorator::with_text("first").build(),
+    ///     Decorator::with_text("second item\nwrapped").set("em", 0..6).build(),
+    /// ];
+    /// let text = Decorator::new().bullet_list(items, "bullet").build();
+    /// assert_eq!(text.plain(), "• first\n• second item\n  wrapped\n");
+    /// assert_eq!(text.face_at(0), Some(&"bullet"));
+    /// assert_eq!(text.face_at(14), Some(&"em"));
+    /// ```
+    ///
+    /// [`wrap`]: struct.Text.html#method.wrap
+    pub fn bullet_list<I>(&mut self, items: I, bullet_face: F) -> &mut Self
+    where
+        I: IntoIterator<Item = Text<F>>,
+    {
+        for item in items {
+            self.list_item("• ", &bullet_face, &item);
+        }
+        self
+    }
+
+    /// Appends `items` as a numbered list, as [`bullet_list`] does with the numbers, starting from
+    /// 1, in `number_face`. Numbers are right-aligned so that the items are aligned.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Text};
+    ///
+    /// let items: Vec<Text<u8>> = (1..=10).map(|i| Text::from(i.to_string())).collect();
+    /// let text = Decorator::new().numbered_list(items, 1).build();
+    /// assert!(text.plain().starts_with(" 1. 1\n 2. 2\n"));
+    /// assert!(text.plain().ends_with("\n10. 10\n"));
+    /// ```
+    ///
+    /// [`bullet_list`]: struct.Decorator.html#method.bullet_list
+    pub fn numbered_list<I>(&mut self, items: I, number_face: F) -> &mut Self
+    where
+        I: IntoIterator<Item = Text<F>>,
+    {
+        let items: Vec<Text<F>> = items.into_iter().collect();
+        let width = items.len().to_string().len();
+        for (idx, item) in items.iter().enumerate() {
+            let number = format!("{:>width$}. ", idx + 1, width = width);
+            self.list_item(&number, &number_face, item);
+        }
+        self
+    }
+
+    fn list_item(&mut self, marker: &str, marker_face: &F, item: &Text<F>) {
+        let current_face = self.current_face().clone();
+        let indent = " ".repeat(marker.width());
+        self.set_face(marker_face.clone()).append(marker);
+        for (idx, line) in item.lines().iter().enumerate() {
+            if idx > 0 {
+                self.set_face(F::default()).append("\n").append(&indent);
+            }
+            self.append_text(line);
+        }
+        self.set_face(F::default())
+            .append("\n")
+            .set_face(current_face);
+    }
+}
@@ -0,0 +1,246 @@
+//! Decorated log lines.
+//!
+//! Log events are turned into a [`Text`] with a [`LogFace`] for each part of the line (level,
+//! target, message, fields), which can then be rendered with any renderer.
+//!
+//! [`Text`]: ../struct.Text.html
+//! [`LogFace`]: enum.LogFace.html
+
+use crate::{Decorator, Text};
+
+/// The severity of a log event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// Returns the uppercase name of the level.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+}
+
+fn line_start(level: LogLevel, target: &str) -> Decorator<LogFace> {
+    let mut decorator = Decorator::new();
+    decorator
+        .set_face(LogFace::Level(level))
+        .append(level.as_str())
+        .reset_face()
+        .append(" ")
+        .set_face(LogFace::Target)
+        .append(target)
+        .reset_face()
+        .append(": ");
+    decorator
+}
+
+/// The faces of a decorated log line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum LogFace {
+    /// Separators and padding.
+    #[default]
+    Default,
+    /// The level of the event.
+    Level(LogLevel),
+    /// The target (or module) the event comes from.
+    Target,
+    /// The message of the event.
+    Message,
+    /// The name of a structured field.
+    FieldName,
+    /// The value of a structured field.
+    FieldValue,
+}
+
+#[cfg(feature = "tracing")]
+impl From<&tracing::Level> for LogLevel {
+    fn from(level: &tracing::Level) -> LogLevel {
+        match *level {
+            tracing::Level::ERROR => LogLevel::Error,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::TRACE => LogLevel::Trace,
+        }
+    }
+}
+
+/// A field visitor collecting the message and the fields of a `tracing` event.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+pub struct FieldsVisitor {
+    message: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+#[cfg(feature = "tracing")]
+impl FieldsVisitor {
+    fn push(&mut self, field: &tracing::field::Field, value: String) {
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.push((field.name(), value));
+        }
+    }
+
+    /// Appends the collected message and fields to `decorator`.
+    pub fn append_to(&self, decorator: &mut Decorator<LogFace>) {
+        decorator
+            .set_face(LogFace::Message)
+            .append(&self.message)
+            .reset_face();
+        for (name, value) in &self.fields {
+            decorator
+                .append(" ")
+                .set_face(LogFace::FieldName)
+                .append(name)
+                .reset_face()
+                .append("=")
+                .set_face(LogFace::FieldValue)
+                .append(value)
+                .reset_face();
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.push(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.push(field, format!("{:?}", value));
+    }
+}
+
+/// Returns the decorated line of a `tracing` event: `LEVEL target: message name=value...`.
+///
+/// This is meant to be called from a `Subscriber` or a `Layer`, which then renders the line.
+#[cfg(feature = "tracing")]
+pub fn event_text(event: &tracing::Event<'_>) -> Text<LogFace> {
+    let metadata = event.metadata();
+    let mut visitor = FieldsVisitor::default();
+    event.record(&mut visitor);
+
+    let mut decorator = line_start(metadata.level().into(), metadata.target());
+    visitor.append_to(&mut decorator);
+    decorator.build()
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> LogLevel {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+/// Returns the decorated line of a `log` record: `LEVEL target: message`.
+///
+/// ```
+/// use ornament::logging::{record_text, LogFace, LogLevel};
+///
+/// let text = record_text(
+///     &log::Record::builder()
+///         .level(log::Level::Info)
+///         .target("app::db")
+///         .args(format_args!("connected"))
+///         .build(),
+/// );
+/// assert_eq!(text.plain(), "INFO app::db: connected");
+/// assert_eq!(text.face_at(0), Some(&LogFace::Level(LogLevel::Info)));
+/// assert_eq!(text.face_at(5), Some(&LogFace::Target));
+/// ```
+#[cfg(feature = "log")]
+pub fn record_text(record: &log::Record<'_>) -> Text<LogFace> {
+    let mut decorator = line_start(record.level().into(), record.target());
+    decorator
+        .set_face(LogFace::Message)
+        .append(&record.args().to_string());
+    decorator.build()
+}
+
+/// A logger printing decorated records to the standard error, rendered by a user-chosen
+/// renderer.
+///
+/// ```no_run
+/// use ornament::logging::{LogFace, TextLogger};
+///
+/// TextLogger::new(|text| {
+///     text.render(|tf| match tf.face {
+///         LogFace::Target => format!("\x1b[2m{}\x1b[0m", tf.text),
+///         _ => tf.text.clone(),
+///     })
+/// })
+/// .init()
+/// .unwrap();
+/// log::info!("ready");
+/// ```
+#[cfg(feature = "log")]
+pub struct TextLogger<R> {
+    renderer: R,
+    level: log::LevelFilter,
+}
+
+#[cfg(feature = "log")]
+impl<R> TextLogger<R>
+where
+    R: Fn(&Text<LogFace>) -> String + Send + Sync + 'static,
+{
+    /// Creates a new `TextLogger` logging all the records through `renderer`.
+    pub fn new(renderer: R) -> TextLogger<R> {
+        TextLogger {
+            renderer,
+            level: log::LevelFilter::Trace,
+        }
+    }
+
+    /// Sets the maximum level of logged records.
+    pub fn with_level(mut self, level: log::LevelFilter) -> TextLogger<R> {
+        self.level = level;
+        self
+    }
+
+    /// Installs the logger as the global logger.
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        log::set_max_level(self.level);
+        log::set_boxed_logger(Box::new(self))
+    }
+}
+
+#[cfg(feature = "log")]
+impl<R> log::Log for TextLogger<R>
+where
+    R: Fn(&Text<LogFace>) -> String + Send + Sync,
+{
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}", (self.renderer)(&record_text(record)));
+        }
+    }
+
+    fn flush(&self) {}
+}
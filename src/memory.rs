@@ -0,0 +1,35 @@
+/// A memory usage report of a [`Text`] or a [`Decorator`].
+///
+/// [`Text`]: struct.Text.html
+/// [`Decorator`]: struct.Decorator.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The number of fragments. For a `Decorator` this is the number of nodes of the decoration
+    /// structure.
+    pub fragments: usize,
+    /// The length of the text, in bytes.
+    pub text_bytes: usize,
+    /// The other allocated bytes: fragment storage and unused capacity.
+    pub overhead: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total allocated bytes.
+    pub fn total(&self) -> usize {
+        self.text_bytes + self.overhead
+    }
+}
+
+/// Statistics about the decoration structure of a [`Decorator`], to detect builders whose
+/// structure grows too much.
+///
+/// [`Decorator`]: struct.Decorator.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecorationStats {
+    /// The number of nodes of the decoration structure.
+    pub nodes: usize,
+    /// The nesting depth of the decoration structure, the root decoration counting for one.
+    pub depth: usize,
+    /// The number of fragments the structure flattens to.
+    pub fragments: usize,
+}
@@ -0,0 +1,103 @@
+use crate::buffer::Buffer;
+use crate::{Decorator, FaceMerge};
+use std::ops::RangeBounds;
+
+/// A face carrying user metadata along the face itself (e.g. a URL, a diagnostic ID, a tooltip).
+///
+/// Using it as the face of a [`Decorator`] makes [`attach`] available, which assigns metadata to a
+/// range without touching the faces. Renderers and exporters read the metadata from the fragment
+/// face.
+///
+/// ```
+/// use ornament::{Decorator, WithMeta};
+///
+/// let text = Decorator::<WithMeta<(), &str>>::with_text("See the docs.")
+///     .attach("https://docs.rs/ornament", 8..12)
+///     .build();
+/// let rendered = text.render(|tf| match tf.face.meta {
+///     Some(url) => format!("[{}]({})", tf.text, url),
+///     None => tf.text.clone(),
+/// });
+/// assert_eq!(rendered, "See the [docs](https://docs.rs/ornament).");
+/// ```
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`attach`]: struct.Decorator.html#method.attach
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct WithMeta<F, M> {
+    /// The face.
+    pub face: F,
+    /// The metadata, if any.
+    pub meta: Option<M>,
+}
+
+impl<F, M> WithMeta<F, M> {
+    /// Creates a new `WithMeta` with the given face and metadata.
+    pub fn new(face: F, meta: M) -> WithMeta<F, M> {
+        WithMeta {
+            face,
+            meta: Some(meta),
+        }
+    }
+}
+
+impl<F: Default, M> Default for WithMeta<F, M> {
+    fn default() -> Self {
+        F::default().into()
+    }
+}
+
+impl<F, M> From<F> for WithMeta<F, M> {
+    fn from(face: F) -> WithMeta<F, M> {
+        WithMeta { face, meta: None }
+    }
+}
+
+impl<F, M> FaceMerge for WithMeta<F, M>
+where
+    F: FaceMerge,
+    M: Clone,
+{
+    fn merge(&self, other: &Self) -> Self {
+        WithMeta {
+            face: self.face.merge(&other.face),
+            meta: other.meta.clone().or_else(|| self.meta.clone()),
+        }
+    }
+}
+
+impl<F, M, B> Decorator<WithMeta<F, M>, B>
+where
+    F: Clone + Default + PartialEq,
+    M: Clone + PartialEq,
+    B: Buffer,
+{
+    /// Assigns `meta` to the given range, keeping the faces previously assigned to it.
+    ///
+    /// This method is chainable.
+    pub fn attach<R: RangeBounds<usize>>(&mut self, meta: M, range: R) -> &mut Self {
+        self.replace_meta(Some(meta), range)
+    }
+
+    /// Removes the metadata assigned to the given range, keeping the faces previously assigned to
+    /// it.
+    ///
+    /// This method is chainable.
+    pub fn detach<R: RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
+        self.replace_meta(None, range)
+    }
+
+    fn replace_meta<R: RangeBounds<usize>>(&mut self, meta: Option<M>, range: R) -> &mut Self {
+        if let Some(safe_range) = self.safe_range(range) {
+            for (old, sub_range) in self.decorations.faces_in(safe_range) {
+                let face = WithMeta {
+                    face: old.face,
+                    meta: meta.clone(),
+                };
+                self.apply(face, sub_range);
+            }
+        }
+        self
+    }
+}
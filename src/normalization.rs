@@ -0,0 +1,65 @@
+use crate::{Decorator, Text};
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Returns the decorated text in Unicode Normalization Form C (canonical composition).
+    ///
+    /// Fragments are normalized one by one. Combining marks starting a fragment are moved to the
+    /// previous one so that they can be composed with their base character, whose face they take.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("cafe\u{301} ok").set(1, 0..6).build();
+    /// let normalized = text.nfc();
+    /// assert_eq!(normalized.plain(), "café ok");
+    /// assert_eq!(normalized.face_at(4), Some(&1));
+    /// ```
+    pub fn nfc(&self) -> Text<F> {
+        self.normalized(|text| text.nfc().collect())
+    }
+
+    /// Returns the decorated text in Unicode Normalization Form D (canonical decomposition).
+    ///
+    /// Fragments are normalized one by one, as with [`nfc`].
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("café").set(1, 3..5).build();
+    /// let normalized = text.nfd();
+    /// assert_eq!(normalized.plain(), "cafe\u{301}");
+    /// assert_eq!(normalized.face_at(4), Some(&1));
+    /// ```
+    ///
+    /// [`nfc`]: struct.Text.html#method.nfc
+    pub fn nfd(&self) -> Text<F> {
+        self.normalized(|text| text.nfd().collect())
+    }
+
+    fn normalized<N>(&self, normalize: N) -> Text<F>
+    where
+        N: Fn(&str) -> String,
+    {
+        let mut pieces: Vec<(String, &F)> = Vec::new();
+        for tf in self {
+            let marks = tf
+                .text
+                .find(|c| canonical_combining_class(c) == 0)
+                .unwrap_or(tf.text.len());
+            match pieces.last_mut() {
+                Some((text, _)) => text.push_str(&tf.text[..marks]),
+                None => pieces.push((tf.text[..marks].to_owned(), &tf.face)),
+            }
+            if marks < tf.text.len() {
+                pieces.push((tf.text[marks..].to_owned(), &tf.face));
+            }
+        }
+        let mut decorator = Decorator::new();
+        for (text, face) in pieces {
+            decorator.set_face(face.clone()).append(&normalize(&text));
+        }
+        decorator.build()
+    }
+}
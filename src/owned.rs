@@ -0,0 +1,219 @@
+use crate::{Buffer, Decorator, Text, TextFragment};
+use std::ops::{Deref, DerefMut, Range, RangeBounds};
+
+/// A [`Decorator`] with a consuming builder API: its methods take the decorator by value and
+/// return it, so that it can be built in a single expression or returned from helpers.
+///
+/// The methods of [`Decorator`] which are not mirrored here can be reached through [`apply`] or
+/// by dereferencing.
+///
+/// ```
+/// use ornament::DecoratorOwned;
+///
+/// fn header(title: &str) -> DecoratorOwned<&'static str> {
+///     DecoratorOwned::new().set_face("title").append(title).reset_face().append("\n")
+/// }
+///
+/// let text = header("Usage").append("ornament [FILE]").set("arg", 15..21).build();
+/// assert_eq!(text.plain(), "Usage\nornament [FILE]");
+/// assert_eq!(text.face_at(0), Some(&"title"));
+/// assert_eq!(text.face_at(15), Some(&"arg"));
+/// ```
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`apply`]: struct.DecoratorOwned.html#method.apply
+pub struct DecoratorOwned<F: Default, B = String>(Decorator<F, B>);
+
+impl<F> DecoratorOwned<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    /// Creates a new empty `DecoratorOwned`.
+    pub fn new() -> DecoratorOwned<F> {
+        DecoratorOwned(Decorator::new())
+    }
+
+    /// Creates a new `DecoratorOwned` initialized with `text`.
+    pub fn with_text(text: &str) -> DecoratorOwned<F> {
+        DecoratorOwned(Decorator::with_text(text))
+    }
+}
+
+impl<F, B> DecoratorOwned<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Creates a new `DecoratorOwned` using `buffer` as text storage, see
+    /// [`Decorator::with_buffer`].
+    ///
+    /// [`Decorator::with_buffer`]: struct.Decorator.html#method.with_buffer
+    pub fn with_buffer(buffer: B) -> DecoratorOwned<F, B> {
+        DecoratorOwned(Decorator::with_buffer(buffer))
+    }
+
+    /// Sets the current face, see [`Decorator::set_face`].
+    ///
+    /// [`Decorator::set_face`]: struct.Decorator.html#method.set_face
+    pub fn set_face(mut self, face: F) -> Self {
+        self.0.set_face(face);
+        self
+    }
+
+    /// Resets the current face, see [`Decorator::reset_face`].
+    ///
+    /// [`Decorator::reset_face`]: struct.Decorator.html#method.reset_face
+    pub fn reset_face(mut self) -> Self {
+        self.0.reset_face();
+        self
+    }
+
+    /// Saves the current face and sets `face` as the current face, see
+    /// [`Decorator::push_face`].
+    ///
+    /// [`Decorator::push_face`]: struct.Decorator.html#method.push_face
+    pub fn push_face(mut self, face: F) -> Self {
+        self.0.push_face(face);
+        self
+    }
+
+    /// Restores the last saved face, see [`Decorator::pop_face`].
+    ///
+    /// [`Decorator::pop_face`]: struct.Decorator.html#method.pop_face
+    pub fn pop_face(mut self) -> Self {
+        self.0.pop_face();
+        self
+    }
+
+    /// Appends `text` with the current face, see [`Decorator::append`].
+    ///
+    /// [`Decorator::append`]: struct.Decorator.html#method.append
+    pub fn append(mut self, text: &str) -> Self {
+        self.0.append(text);
+        self
+    }
+
+    /// Appends the text of `fragment` with its face, see [`Decorator::append_fragment`].
+    ///
+    /// [`Decorator::append_fragment`]: struct.Decorator.html#method.append_fragment
+    pub fn append_fragment(mut self, fragment: TextFragment<F>) -> Self {
+        self.0.append_fragment(fragment);
+        self
+    }
+
+    /// Appends every fragment of `text` with its face, see [`Decorator::append_text`].
+    ///
+    /// [`Decorator::append_text`]: struct.Decorator.html#method.append_text
+    pub fn append_text(mut self, text: &Text<F>) -> Self {
+        self.0.append_text(text);
+        self
+    }
+
+    /// Assigns `face` to the given range, see [`Decorator::set`].
+    ///
+    /// [`Decorator::set`]: struct.Decorator.html#method.set
+    pub fn set<R: RangeBounds<usize>>(mut self, face: F, range: R) -> Self {
+        self.0.set(face, range);
+        self
+    }
+
+    /// Assigns `face` to the given range of `char`s, see [`Decorator::set_chars`].
+    ///
+    /// [`Decorator::set_chars`]: struct.Decorator.html#method.set_chars
+    pub fn set_chars(mut self, face: F, range: Range<usize>) -> Self {
+        self.0.set_chars(face, range);
+        self
+    }
+
+    /// Assigns `face` to the given range with a priority, see [`Decorator::set_with_priority`].
+    ///
+    /// [`Decorator::set_with_priority`]: struct.Decorator.html#method.set_with_priority
+    pub fn set_with_priority<R>(mut self, face: F, range: R, priority: u32) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.0.set_with_priority(face, range, priority);
+        self
+    }
+
+    /// Runs `f` on the wrapped decorator, to call the methods which are not mirrored here.
+    ///
+    /// ```
+    /// use ornament::DecoratorOwned;
+    ///
+    /// let text = DecoratorOwned::with_text("quote")
+    ///     .apply(|d| {
+    ///         d.insert_with_face(0, "> ", "marker");
+    ///     })
+    ///     .build();
+    /// assert_eq!(text.plain(), "> quote");
+    /// assert_eq!(text.face_at(0), Some(&"marker"));
+    /// ```
+    pub fn apply<T>(mut self, f: T) -> Self
+    where
+        T: FnOnce(&mut Decorator<F, B>),
+    {
+        f(&mut self.0);
+        self
+    }
+
+    /// Builds the decorated text.
+    pub fn build(mut self) -> Text<F> {
+        self.0.build()
+    }
+
+    /// Returns the wrapped decorator.
+    pub fn into_inner(self) -> Decorator<F, B> {
+        self.0
+    }
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Converts the decorator into a [`DecoratorOwned`], to continue building it with a
+    /// consuming API.
+    ///
+    /// [`DecoratorOwned`]: struct.DecoratorOwned.html
+    pub fn into_owned(self) -> DecoratorOwned<F, B> {
+        DecoratorOwned(self)
+    }
+}
+
+impl<F, B> Default for DecoratorOwned<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    fn default() -> DecoratorOwned<F, B> {
+        DecoratorOwned::with_buffer(B::default())
+    }
+}
+
+impl<F: Default, B> From<Decorator<F, B>> for DecoratorOwned<F, B> {
+    fn from(decorator: Decorator<F, B>) -> DecoratorOwned<F, B> {
+        DecoratorOwned(decorator)
+    }
+}
+
+impl<F: Default, B> From<DecoratorOwned<F, B>> for Decorator<F, B> {
+    fn from(decorator: DecoratorOwned<F, B>) -> Decorator<F, B> {
+        decorator.0
+    }
+}
+
+impl<F: Default, B> Deref for DecoratorOwned<F, B> {
+    type Target = Decorator<F, B>;
+
+    fn deref(&self) -> &Decorator<F, B> {
+        &self.0
+    }
+}
+
+impl<F: Default, B> DerefMut for DecoratorOwned<F, B> {
+    fn deref_mut(&mut self) -> &mut Decorator<F, B> {
+        &mut self.0
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Text;
+use owo_colors::{OwoColorize, Style};
+
+/// A face which can be displayed with an `owo_colors` style.
+///
+/// ```
+/// use ornament::{Decorator, OwoFace};
+/// use owo_colors::Style;
+///
+/// #[derive(Clone, Debug, Default, PartialEq)]
+/// enum Face {
+///     #[default]
+///     Plain,
+///     Error,
+/// }
+///
+/// impl OwoFace for Face {
+///     fn owo_style(&self) -> Style {
+///         match self {
+///             Face::Plain => Style::new(),
+///             Face::Error => Style::new().red().bold(),
+///         }
+///     }
+/// }
+///
+/// let text = Decorator::with_text("error: not found").set(Face::Error, 0..5).build();
+/// assert_eq!(text.render_owo(), "\x1b[31;1merror\x1b[0m: not found");
+/// ```
+pub trait OwoFace {
+    /// Returns the style of the face.
+    fn owo_style(&self) -> Style;
+}
+
+impl OwoFace for Style {
+    fn owo_style(&self) -> Style {
+        *self
+    }
+}
+
+impl<F: OwoFace> Text<F> {
+    /// Renders the decorated text with the `owo_colors` style of each face.
+    pub fn render_owo(&self) -> String {
+        self.render(|tf| {
+            let style = tf.face.owo_style();
+            if style.is_plain() {
+                tf.text.clone()
+            } else {
+                tf.text.style(style).to_string()
+            }
+        })
+    }
+}
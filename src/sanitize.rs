@@ -0,0 +1,80 @@
+use crate::{Decorator, Text};
+use std::ops::Range;
+
+/// Returns the byte ranges of the terminal control sequences and characters of `s`, line breaks
+/// and tabs excepted.
+fn control_ranges(s: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        // Strings (OSC, DCS, SOS, PM, APC) end with BEL or ST, CSI sequences with a final byte.
+        let (string, csi) = match c {
+            '\n' | '\t' => continue,
+            '\r' if matches!(chars.peek(), Some((_, '\n'))) => continue,
+            '\x1b' => match chars.peek().map(|&(_, c)| c) {
+                Some('[') => (false, true),
+                Some(']' | 'P' | 'X' | '^' | '_') => (true, false),
+                _ => (false, false),
+            },
+            '\u{9b}' => (false, true),
+            '\u{90}' | '\u{98}' | '\u{9d}' | '\u{9e}' | '\u{9f}' => (true, false),
+            c if c.is_control() => {
+                ranges.push(start..start + c.len_utf8());
+                continue;
+            }
+            _ => continue,
+        };
+        if c == '\x1b' {
+            chars.next_if(|&(_, c)| (' '..='~').contains(&c));
+        }
+        let mut end = chars.peek().map_or(s.len(), |&(idx, _)| idx);
+        if string {
+            while let Some((idx, c)) = chars.next() {
+                end = idx + c.len_utf8();
+                if c == '\x07' || c == '\u{9c}' {
+                    break;
+                }
+                if c == '\x1b' && chars.next_if(|&(_, c)| c == '\\').is_some() {
+                    end += 1;
+                    break;
+                }
+            }
+        } else if csi {
+            while let Some((idx, c)) = chars.next_if(|&(_, c)| ('\x20'..='\x3f').contains(&c)) {
+                end = idx + c.len_utf8();
+            }
+            if let Some((idx, c)) = chars.next_if(|&(_, c)| ('\x40'..='\x7e').contains(&c)) {
+                end = idx + c.len_utf8();
+            }
+        }
+        ranges.push(start..end);
+    }
+    ranges
+}
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Returns the decorated text without its terminal control sequences and characters, such
+    /// as escape sequences embedded in untrusted input, so that it can be rendered safely. Line
+    /// breaks and tabs are kept, carriage returns only when they are followed by a line break.
+    ///
+    /// The remaining text keeps its decorations.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("user: \x1b[2J\x1b]0;pwned\x07hi\x08!\r\n")
+    ///     .set("msg", 6..26)
+    ///     .build();
+    /// let sanitized = text.sanitize();
+    /// assert_eq!(sanitized.plain(), "user: hi!\r\n");
+    /// assert_eq!(sanitized.face_at(6), Some(&"msg"));
+    /// ```
+    pub fn sanitize(&self) -> Text<F> {
+        let edits: Vec<(Range<usize>, String)> = control_ranges(&self.plain())
+            .into_iter()
+            .map(|range| (range, String::new()))
+            .collect();
+        let mut decorator = Decorator::from_text(self.clone());
+        decorator.apply_edits(&edits).build()
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{Buffer, Decorator, Text};
+use std::ops::Range;
+
+impl<F> Text<F> {
+    /// Returns an iterator over the byte ranges of the non-overlapping occurrences of `pattern`
+    /// in the underlying text, as `str::match_indices` does. Occurrences may span several
+    /// fragments.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("an apple, a banana").set(1, 4..13).build();
+    /// let ranges: Vec<_> = text.find_iter("a").collect();
+    /// assert_eq!(ranges, vec![0..1, 3..4, 10..11, 13..14, 15..16, 17..18]);
+    /// ```
+    pub fn find_iter(&self, pattern: &str) -> impl Iterator<Item = Range<usize>> {
+        self.plain()
+            .match_indices(pattern)
+            .map(|(start, m)| start..start + m.len())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns an iterator over the byte ranges of the matches of `regex` in the underlying
+    /// text, as `Regex::find_iter` does.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use regex::Regex;
+    ///
+    /// let text = Decorator::<()>::with_text("v1.2 and v10.0").build();
+    /// let versions = Regex::new(r"v\d+\.\d+").unwrap();
+    /// assert_eq!(text.find_regex_iter(&versions).collect::<Vec<_>>(), vec![0..4, 9..14]);
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_regex_iter(&self, regex: &regex::Regex) -> impl Iterator<Item = Range<usize>> {
+        regex
+            .find_iter(&self.plain())
+            .map(|m| m.range())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Returns the decorated text with the non-overlapping occurrences of `pattern` decorated
+    /// with `face`, such as search results. Occurrences may span several fragments.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("needle in a haystack of needles")
+    ///     .set("bold", 3..9)
+    ///     .build();
+    /// let highlighted = text.highlight_matches("needle", "match");
+    /// assert_eq!(highlighted.face_at(0), Some(&"match"));
+    /// assert_eq!(highlighted.face_at(6), Some(&"bold"));
+    /// assert_eq!(highlighted.face_at(24), Some(&"match"));
+    /// assert_eq!(highlighted.face_at(30), Some(&""));
+    /// ```
+    pub fn highlight_matches(&self, pattern: &str, face: F) -> Text<F> {
+        if pattern.is_empty() {
+            return self.clone();
+        }
+        let mut decorator = Decorator::from_text(self.clone());
+        for range in self.find_iter(pattern) {
+            decorator.set(face.clone(), range);
+        }
+        decorator.build()
+    }
+}
+
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Assigns `face` to the non-overlapping occurrences of `pattern` in the text appended so
+    /// far, as [`set`] does.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("[WARN] disk full\n[ERROR] disk lost")
+    ///     .find_and_set("disk", "device")
+    ///     .find_and_set("[ERROR]", "error")
+    ///     .build();
+    /// assert_eq!(text.face_at(7), Some(&"device"));
+    /// assert_eq!(text.face_at(17), Some(&"error"));
+    /// assert_eq!(text.face_at(25), Some(&"device"));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    pub fn find_and_set(&mut self, pattern: &str, face: F) -> &mut Self {
+        if pattern.is_empty() {
+            return self;
+        }
+        let plain = self.text.slice(0..self.text.len()).into_owned();
+        let ranges = plain
+            .match_indices(pattern)
+            .map(|(start, m)| (face.clone(), start..start + m.len()));
+        self.set_many(ranges)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<F, B> Decorator<F, B>
+where
+    F: Clone + Default + PartialEq,
+    B: Buffer,
+{
+    /// Assigns `face` to the matches of `regex` in the text appended so far, as [`set`] does.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use regex::Regex;
+    ///
+    /// let ip = Regex::new(r"\d+\.\d+\.\d+\.\d+").unwrap();
+    /// let text = Decorator::with_text("connected to 10.0.0.1")
+    ///     .regex_set(&ip, "ip")
+    ///     .build();
+    /// assert_eq!(text.face_at(13), Some(&"ip"));
+    /// ```
+    ///
+    /// [`set`]: struct.Decorator.html#method.set
+    pub fn regex_set(&mut self, regex: &regex::Regex, face: F) -> &mut Self {
+        let plain = self.text.slice(0..self.text.len()).into_owned();
+        let ranges = regex.find_iter(&plain).map(|m| (face.clone(), m.range()));
+        self.set_many(ranges)
+    }
+
+    /// Assigns faces to the capture groups of the matches of `regex` in the text appended so
+    /// far. `face` is given the index and the name of each group, `0` being the whole match, and
+    /// returns the face to assign to it, if any. Nested groups win over the groups containing
+    /// them.
+    ///
+    /// This method is chainable.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use regex::Regex;
+    ///
+    /// let line = Regex::new(r"(?P<time>\d\d:\d\d) (?P<level>[A-Z]+)").unwrap();
+    /// let text = Decorator::with_text("12:03 INFO started\n12:04 WARN slow")
+    ///     .regex_set_groups(&line, |_, name| match name {
+    ///         Some("time") => Some("time"),
+    ///         Some("level") => Some("level"),
+    ///         _ => None,
+    ///     })
+    ///     .build();
+    /// assert_eq!(text.face_at(0), Some(&"time"));
+    /// assert_eq!(text.face_at(6), Some(&"level"));
+    /// assert_eq!(text.face_at(11), Some(&""));
+    /// assert_eq!(text.face_at(25), Some(&"level"));
+    /// ```
+    pub fn regex_set_groups<M>(&mut self, regex: &regex::Regex, face: M) -> &mut Self
+    where
+        M: Fn(usize, Option<&str>) -> Option<F>,
+    {
+        let faces: Vec<Option<F>> = regex
+            .capture_names()
+            .enumerate()
+            .map(|(idx, name)| face(idx, name))
+            .collect();
+        let plain = self.text.slice(0..self.text.len()).into_owned();
+        let mut ranges = Vec::new();
+        for captures in regex.captures_iter(&plain) {
+            for (idx, group_face) in faces.iter().enumerate() {
+                if let (Some(group_face), Some(m)) = (group_face, captures.get(idx)) {
+                    ranges.push((group_face.clone(), m.range()));
+                }
+            }
+        }
+        self.set_many(ranges)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Returns the decorated text with the matches of `regex` replaced by `replacement`, in which
+    /// `$name` and `$1` are substituted by the captures as described in `Captures::expand`.
+    ///
+    /// The surrounding decorations are kept. Replacements are decorated with `face` when it is
+    /// given, otherwise with the face found at the start of the match.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use regex::Regex;
+    ///
+    /// let text = Decorator::with_text("2024-05-17 released").set("date", 0..10).build();
+    /// let date = Regex::new(r"(\d+)-(\d+)-(\d+)").unwrap();
+    /// let replaced = text.replace_regex(&date, "$3/$2/$1", None);
+    /// assert_eq!(replaced.plain(), "17/05/2024 released");
+    /// assert_eq!(replaced.face_at(0), Some(&"date"));
+    ///
+    /// let replaced = text.replace_regex(&date, "[$0]", Some("edited"));
+    /// assert_eq!(replaced.plain(), "[2024-05-17] released");
+    /// assert_eq!(replaced.face_at(11), Some(&"edited"));
+    /// assert_eq!(replaced.face_at(12), Some(&""));
+    /// ```
+    pub fn replace_regex(
+        &self,
+        regex: &regex::Regex,
+        replacement: &str,
+        face: Option<F>,
+    ) -> Text<F> {
+        let plain = self.plain();
+        let mut edits = Vec::new();
+        for captures in regex.captures_iter(&plain) {
+            let range = captures.get(0).map_or(0..0, |m| m.range());
+            let mut text = String::new();
+            captures.expand(replacement, &mut text);
+            edits.push((range, text));
+        }
+
+        let mut decorator = Decorator::from_text(self.clone());
+        decorator.apply_edits(&edits);
+        if let Some(face) = face {
+            let mut shift = 0isize;
+            for (range, text) in &edits {
+                let start = (range.start as isize + shift) as usize;
+                decorator.set(face.clone(), start..start + text.len());
+                shift += text.len() as isize - range.len() as isize;
+            }
+        }
+        decorator.build()
+    }
+}
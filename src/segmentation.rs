@@ -0,0 +1,29 @@
+use crate::Text;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+impl<F: Clone> Text<F> {
+    /// Returns the decorated text in the given range of extended grapheme clusters, so that
+    /// emojis, flags and combining sequences are never split. The range is clamped to the number
+    /// of graphemes of the text.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("🇫🇷 cafe\u{301}!").set(1, 9..15).build();
+    /// let sliced = text.slice_graphemes(0..6);
+    /// assert_eq!(sliced.plain(), "🇫🇷 cafe\u{301}");
+    /// assert_eq!(sliced.face_at(9), Some(&1));
+    /// assert_eq!(text.slice_graphemes(1..2).plain(), " ");
+    /// ```
+    pub fn slice_graphemes(&self, range: Range<usize>) -> Text<F> {
+        let plain = self.plain();
+        let offset = |idx: usize| {
+            plain
+                .grapheme_indices(true)
+                .nth(idx)
+                .map_or(plain.len(), |(offset, _)| offset)
+        };
+        self.slice(offset(range.start)..offset(range.end))
+    }
+}
@@ -0,0 +1,168 @@
+//! Diagnostic snippets: source lines annotated with labeled spans.
+//!
+//! ```
+//! use ornament::snippet::Snippet;
+//!
+//! let source = "let a = 1;\nlet b = a + c;\n";
+//! let text = Snippet::new(source)
+//!     .gutter_face("gutter")
+//!     .label(19..20, "warning", "used here")
+//!     .label(23..24, "error", "not found")
+//!     .build();
+//! assert_eq!(
+//!     text.plain(),
+//!     concat!(
+//!         "2 | let b = a + c;\n",
+//!         "  |         ^ used here\n",
+//!         "  |             ^ not found\n",
+//!     )
+//! );
+//! assert_eq!(text.face_at(12), Some(&"warning"));
+//! ```
+
+use crate::{Decorator, Text};
+use std::cmp::{max, min};
+use std::ops::Range;
+
+struct Label<F> {
+    range: Range<usize>,
+    face: F,
+    message: String,
+}
+
+/// A builder of a decorated diagnostic snippet.
+///
+/// Only the lines covered by a label are displayed, prefixed by a gutter with their line number.
+/// Each label is underlined with carets on its own row below its line, followed by its message.
+/// A label spanning several lines is underlined up to the end of its first line.
+///
+/// Columns are computed assuming each `char` is displayed on a single column.
+pub struct Snippet<'a, F> {
+    source: &'a str,
+    first_line: usize,
+    gutter_face: F,
+    labels: Vec<Label<F>>,
+}
+
+impl<'a, F> Snippet<'a, F>
+where
+    F: Clone + Default + PartialEq,
+{
+    /// Creates a new `Snippet` of `source`, without labels.
+    pub fn new(source: &'a str) -> Snippet<'a, F> {
+        Snippet {
+            source,
+            first_line: 1,
+            gutter_face: F::default(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Sets the number of the first line of `source`, defaults to `1`.
+    pub fn first_line(mut self, number: usize) -> Snippet<'a, F> {
+        self.first_line = number;
+        self
+    }
+
+    /// Sets the face of the gutter, defaults to `F::default()`.
+    pub fn gutter_face(mut self, face: F) -> Snippet<'a, F> {
+        self.gutter_face = face;
+        self
+    }
+
+    /// Adds a label on the given byte range of `source`. The range is decorated with `face` in
+    /// the source line and in the underline row.
+    ///
+    /// The range is clamped to `source` and widened to the `char` boundaries around its bounds.
+    pub fn label(mut self, range: Range<usize>, face: F, message: &str) -> Snippet<'a, F> {
+        let mut start = min(range.start, self.source.len());
+        while !self.source.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = max(start, min(range.end, self.source.len()));
+        while !self.source.is_char_boundary(end) {
+            end += 1;
+        }
+        self.labels.push(Label {
+            range: start..end,
+            face,
+            message: message.to_owned(),
+        });
+        self
+    }
+
+    /// Returns the decorated snippet.
+    pub fn build(&self) -> Text<F> {
+        // The lines with their number, offset and content, without the line break.
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for (idx, line) in self.source.split_inclusive('\n').enumerate() {
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+            lines.push((self.first_line + idx, offset, content));
+            offset += line.len();
+        }
+        if self.source.is_empty() || self.source.ends_with('\n') {
+            // The empty line after the last line break, where a label at the end points.
+            lines.push((self.first_line + lines.len(), offset, ""));
+        }
+
+        let mut shown: Vec<(usize, usize, &str, Vec<&Label<F>>)> = Vec::new();
+        for (idx, &(number, start, content)) in lines.iter().enumerate() {
+            // A line owns its line break, the last one everything after it.
+            let next = lines.get(idx + 1).map_or(usize::MAX, |&(_, next, _)| next);
+            let mut labels: Vec<&Label<F>> = self
+                .labels
+                .iter()
+                .filter(|l| start <= l.range.start && l.range.start < next)
+                .collect();
+            if !labels.is_empty() {
+                labels.sort_by_key(|l| l.range.start);
+                shown.push((number, start, content, labels));
+            }
+        }
+        let width = shown
+            .last()
+            .map_or(1, |(number, ..)| number.to_string().len());
+
+        let mut decorator = Decorator::new();
+        for (number, start, content, labels) in shown {
+            decorator
+                .set_face(self.gutter_face.clone())
+                .append(&format!("{:>width$} | ", number, width = width))
+                .reset_face();
+            let line_start = decorator.len();
+            decorator.append(content).append("\n");
+            // The label bounds relative to the line, clamped to its content.
+            let bounds = |label: &Label<F>| {
+                let end = min(label.range.end - start, content.len());
+                (min(label.range.start - start, end), end)
+            };
+            for label in &labels {
+                let (label_start, end) = bounds(label);
+                decorator.set(
+                    label.face.clone(),
+                    line_start + label_start..line_start + end,
+                );
+            }
+
+            for label in labels {
+                let (label_start, end) = bounds(label);
+                let column = content[..label_start].chars().count();
+                let carets = max(1, content[label_start..end].chars().count());
+                decorator
+                    .set_face(self.gutter_face.clone())
+                    .append(&format!("{:>width$} | ", "", width = width))
+                    .reset_face()
+                    .append(&" ".repeat(column))
+                    .set_face(label.face.clone())
+                    .append(&"^".repeat(carets));
+                if !label.message.is_empty() {
+                    decorator.append(" ").append(&label.message);
+                }
+                decorator.reset_face().append("\n");
+            }
+        }
+        decorator.build()
+    }
+}
@@ -0,0 +1,111 @@
+use crate::decorations::Decorations;
+use crate::{bounded, Text, TextFragment};
+use std::ops::{Range, RangeBounds};
+
+/// The face assignations of a text, without the text itself.
+///
+/// This is the decoration algebra used by the [`Decorator`]: lengths are appended with a face,
+/// then faces are assigned to ranges, the last assignation winning where ranges overlap. The
+/// decorated text is obtained by giving the text to [`to_text`].
+///
+/// ```
+/// use ornament::Spans;
+///
+/// let mut spans = Spans::new();
+/// spans.append("keyword", 3).append("", 7).set("var", 4..5);
+/// assert_eq!(spans.flatten(), vec![("keyword", 0..3), ("", 3..4), ("var", 4..5), ("", 5..10)]);
+///
+/// let text = spans.to_text("let x = 1;");
+/// assert_eq!(text.face_at(4), Some(&"var"));
+/// ```
+///
+/// [`Decorator`]: struct.Decorator.html
+/// [`to_text`]: struct.Spans.html#method.to_text
+#[derive(Clone)]
+pub struct Spans<F> {
+    decorations: Decorations<F>,
+}
+
+impl<F> Spans<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    /// Creates new empty `Spans`.
+    pub fn new() -> Spans<F> {
+        Spans {
+            decorations: Decorations::new(F::default()),
+        }
+    }
+
+    /// Returns the length covered by the spans, in bytes.
+    pub fn len(&self) -> usize {
+        self.decorations.len()
+    }
+
+    /// Returns `true` if the spans cover no byte.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `len` bytes with `face`.
+    ///
+    /// This method is chainable.
+    pub fn append(&mut self, face: F, len: usize) -> &mut Self {
+        if len > 0 {
+            self.decorations.append(face, len);
+        }
+        self
+    }
+
+    /// Assigns `face` to the given range. It overrides all faces previously assigned to this
+    /// range. The range is clamped to the length of the spans.
+    ///
+    /// This method is chainable.
+    pub fn set<R: RangeBounds<usize>>(&mut self, face: F, range: R) -> &mut Self {
+        let range = bounded(range, self.len());
+        if range.start < range.end {
+            self.decorations.set(face, range);
+        }
+        self
+    }
+
+    /// Returns the face of each run of bytes, in order, with the range it covers.
+    pub fn flatten(&self) -> Vec<(F, Range<usize>)> {
+        let mut pos = 0;
+        self.decorations
+            .flatten_from(0)
+            .into_iter()
+            .map(|(face, len)| {
+                pos += len;
+                (face, pos - len..pos)
+            })
+            .collect()
+    }
+
+    /// Returns `text` decorated with the faces of the spans.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `text` is not the one of the spans or if a run doesn't start on a
+    /// `char` boundary.
+    pub fn to_text(&self, text: &str) -> Text<F> {
+        assert_eq!(text.len(), self.len(), "text and spans lengths differ");
+        self.flatten()
+            .into_iter()
+            .map(|(face, range)| TextFragment {
+                text: text[range].to_owned(),
+                face,
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<F> Default for Spans<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Text, TextFragment};
+
+/// A decorated text defined at compile time, made of `&'static str` fragments.
+///
+/// Unlike a [`Text`], it can be stored in a `const` or a `static` and doesn't allocate. It is
+/// converted into a `Text` when it needs to be processed further.
+///
+/// ```
+/// use ornament::{StaticText, Text};
+///
+/// static USAGE: StaticText<u8> = StaticText::new(&[("Usage: ", 1), ("app [OPTIONS]", 0)]);
+///
+/// assert_eq!(USAGE.plain(), "Usage: app [OPTIONS]");
+/// let text: Text<u8> = USAGE.into();
+/// assert_eq!(text.face_at(0), Some(&1));
+/// ```
+///
+/// [`Text`]: struct.Text.html
+#[derive(Debug)]
+pub struct StaticText<F: 'static> {
+    fragments: &'static [(&'static str, F)],
+}
+
+impl<F> StaticText<F> {
+    /// Creates a text from its fragments, given as pairs of text and face.
+    pub const fn new(fragments: &'static [(&'static str, F)]) -> StaticText<F> {
+        StaticText { fragments }
+    }
+
+    /// Returns the fragments of the text.
+    pub const fn fragments(&self) -> &'static [(&'static str, F)] {
+        self.fragments
+    }
+
+    /// Returns the length of the underlying text, without decorations, in bytes.
+    pub fn text_len(&self) -> usize {
+        self.fragments.iter().map(|(text, _)| text.len()).sum()
+    }
+
+    /// Converts the decorated text into plain text, stripping all decorations.
+    pub fn plain(&self) -> String {
+        self.fragments.iter().map(|(text, _)| *text).collect()
+    }
+}
+
+impl<F> Clone for StaticText<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for StaticText<F> {}
+
+impl<F: Clone> From<StaticText<F>> for Text<F> {
+    fn from(st: StaticText<F>) -> Text<F> {
+        st.fragments
+            .iter()
+            .map(|(text, face)| TextFragment {
+                text: (*text).to_owned(),
+                face: face.clone(),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<F: Clone> Text<F> {
+    /// Creates a text from static fragments, given as pairs of text and face. See
+    /// [`StaticText`] to define the fragments without allocating.
+    ///
+    /// [`StaticText`]: struct.StaticText.html
+    pub fn from_static(fragments: &'static [(&'static str, F)]) -> Text<F> {
+        StaticText::new(fragments).into()
+    }
+}
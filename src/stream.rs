@@ -0,0 +1,117 @@
+use crate::{FaceWrap, TextFragment};
+
+/// A renderer emitting the output as soon as text is pushed, instead of rendering a built
+/// [`Text`], for live outputs such as logs.
+///
+/// The face of the last pushed text is kept opened across calls: `open` is called when a face
+/// starts and `close` when it ends, which is when text with another face is pushed or when the
+/// renderer is finished.
+///
+/// ```
+/// use ornament::StreamRenderer;
+///
+/// let mut renderer = StreamRenderer::new(
+///     |face: &&str| match *face {
+///         "" => String::new(),
+///         tag => format!("<{}>", tag),
+///     },
+///     |face: &&str| match *face {
+///         "" => String::new(),
+///         tag => format!("</{}>", tag),
+///     },
+/// );
+/// let mut out = renderer.push("b", "err");
+/// out += &renderer.push("b", "or");
+/// out += &renderer.push("", ": ");
+/// out += &renderer.push("i", "oops");
+/// out += &renderer.finish();
+/// assert_eq!(out, "<b>error</b>: <i>oops</i>");
+/// ```
+///
+/// [`Text`]: struct.Text.html
+pub struct StreamRenderer<F, O, C> {
+    open: O,
+    close: C,
+    current: Option<F>,
+}
+
+impl<F, O, C> StreamRenderer<F, O, C>
+where
+    F: PartialEq,
+    O: Fn(&F) -> String,
+    C: Fn(&F) -> String,
+{
+    /// Creates a new `StreamRenderer`, `open` and `close` rendering the start and the end of a
+    /// face.
+    pub fn new(open: O, close: C) -> StreamRenderer<F, O, C> {
+        StreamRenderer {
+            open,
+            close,
+            current: None,
+        }
+    }
+
+    /// Renders `text` with `face`. Empty texts are ignored.
+    pub fn push(&mut self, face: F, text: &str) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        let mut out = String::new();
+        if self.current.as_ref() != Some(&face) {
+            out += &self.finish();
+            out += &(self.open)(&face);
+            self.current = Some(face);
+        }
+        out.push_str(text);
+        out
+    }
+
+    /// Renders a fragment, as [`push`] does.
+    ///
+    /// [`push`]: struct.StreamRenderer.html#method.push
+    pub fn push_fragment(&mut self, tf: &TextFragment<F>) -> String
+    where
+        F: Clone,
+    {
+        self.push(tf.face.clone(), &tf.text)
+    }
+
+    /// Closes the current face. The renderer can be used again afterwards.
+    pub fn finish(&mut self) -> String {
+        match self.current.take() {
+            Some(face) => (self.close)(&face),
+            None => String::new(),
+        }
+    }
+}
+
+impl<F> StreamRenderer<F, fn(&F) -> String, fn(&F) -> String>
+where
+    F: FaceWrap + PartialEq,
+{
+    /// Creates a new `StreamRenderer` opening and closing faces with their prefix and suffix.
+    ///
+    /// ```
+    /// use ornament::{FaceWrap, StreamRenderer};
+    ///
+    /// #[derive(PartialEq)]
+    /// struct Quote;
+    ///
+    /// impl FaceWrap for Quote {
+    ///     fn prefix(&self) -> &str {
+    ///         "«"
+    ///     }
+    ///
+    ///     fn suffix(&self) -> &str {
+    ///         "»"
+    ///     }
+    /// }
+    ///
+    /// let mut renderer = StreamRenderer::wrapped();
+    /// let out = renderer.push(Quote, "a") + &renderer.push(Quote, "b") + &renderer.finish();
+    /// assert_eq!(out, "«ab»");
+    /// ```
+    pub fn wrapped() -> Self {
+        StreamRenderer::new(|f| f.prefix().to_owned(), |f| f.suffix().to_owned())
+    }
+}
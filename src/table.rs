@@ -0,0 +1,231 @@
+//! Tables of decorated cells, laid out by display width. The module is available with the
+//! `unicode-width` feature.
+//!
+//! ```
+//! use ornament::table::{Alignment, Column, Table};
+//! use ornament::Decorator;
+//!
+//! let cell = |text: &str, face| Decorator::with_text(text).set(face, 0..text.len()).build();
+//! let text = Table::new(vec![
+//!     Column::new(),
+//!     Column::new().align(Alignment::Right),
+//!     Column::new().max_width(6),
+//! ])
+//! .separator(" | ", "sep")
+//! .row(vec![cell("name", "head"), cell("size", "head"), cell("kind", "head")])
+//! .row(vec![cell("a.rs", ""), cell("512", "num"), cell("source", "")])
+//! .row(vec![cell("日本.txt", ""), cell("8", "num"), cell("document", "")])
+//! .build();
+//! assert_eq!(
+//!     text.plain(),
+//!     concat!(
+//!         "name     | size | kind  \n",
+//!         "a.rs     |  512 | source\n",
+//!         "日本.txt |    8 | docum…\n",
+//!     )
+//! );
+//! assert_eq!(text.face_at(9), Some(&"sep"));
+//! ```
+
+use crate::{Decorator, Text};
+use std::cmp::{max, min};
+use unicode_width::UnicodeWidthChar;
+
+/// The alignment of the cells of a column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// The layout of a column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Column {
+    alignment: Alignment,
+    min_width: usize,
+    max_width: Option<usize>,
+}
+
+impl Column {
+    /// Creates a new left aligned `Column`, as wide as its widest cell.
+    pub const fn new() -> Column {
+        Column {
+            alignment: Alignment::Left,
+            min_width: 0,
+            max_width: None,
+        }
+    }
+
+    /// Sets the alignment of the cells.
+    pub const fn align(self, alignment: Alignment) -> Column {
+        Column { alignment, ..self }
+    }
+
+    /// Sets the minimum width of the column, narrower cells are padded.
+    pub const fn min_width(self, width: usize) -> Column {
+        Column {
+            min_width: width,
+            ..self
+        }
+    }
+
+    /// Sets the maximum width of the column, wider cells are truncated with an ellipsis.
+    pub const fn max_width(self, width: usize) -> Column {
+        Column {
+            max_width: Some(width),
+            ..self
+        }
+    }
+}
+
+/// A builder of a table of decorated cells.
+///
+/// Widths are display widths: wide characters count for two columns. Cells are expected to be
+/// made of a single line. Missing cells at the end of a row are left empty.
+pub struct Table<F> {
+    columns: Vec<Column>,
+    separator: (String, F),
+    rows: Vec<Vec<Text<F>>>,
+}
+
+impl<F> Table<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    /// Creates a new `Table` with the given columns, without rows.
+    pub fn new(columns: Vec<Column>) -> Table<F> {
+        Table {
+            columns,
+            separator: (" ".to_owned(), F::default()),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Sets the separator between cells and its face, defaults to a single space.
+    pub fn separator(mut self, separator: &str, face: F) -> Table<F> {
+        self.separator = (separator.to_owned(), face);
+        self
+    }
+
+    /// Adds a row of cells. Cells beyond the number of columns are ignored.
+    pub fn row(mut self, cells: Vec<Text<F>>) -> Table<F> {
+        self.rows.push(cells);
+        self
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                let widest = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(idx))
+                    .map(Text::width)
+                    .max()
+                    .unwrap_or(0);
+                let width = max(widest, column.min_width);
+                column
+                    .max_width
+                    .map_or(width, |max_width| min(width, max_width))
+            })
+            .collect()
+    }
+
+    /// Returns the lines of the table, without line breaks.
+    pub fn lines(&self) -> Vec<Text<F>> {
+        let widths = self.widths();
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut decorator = Decorator::new();
+                for (idx, (column, &width)) in self.columns.iter().zip(&widths).enumerate() {
+                    if idx > 0 {
+                        decorator
+                            .set_face(self.separator.1.clone())
+                            .append(&self.separator.0)
+                            .reset_face();
+                    }
+                    match row.get(idx) {
+                        Some(cell) => push_cell(&mut decorator, cell, width, column.alignment),
+                        None => {
+                            decorator.append(&" ".repeat(width));
+                        }
+                    }
+                }
+                decorator.build()
+            })
+            .collect()
+    }
+
+    /// Returns the table, each row ending with a line break.
+    pub fn build(&self) -> Text<F> {
+        let mut decorator = Decorator::new();
+        for line in self.lines() {
+            decorator.append_text(&line).reset_face().append("\n");
+        }
+        decorator.build()
+    }
+}
+
+fn push_cell<F>(decorator: &mut Decorator<F>, cell: &Text<F>, width: usize, alignment: Alignment)
+where
+    F: Clone + Default + PartialEq,
+{
+    let cell_width = cell.width();
+    let (content, used) = if cell_width > width {
+        truncated(cell, width)
+    } else {
+        (cell.clone(), cell_width)
+    };
+    let padding = width - used;
+    let (before, after) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    decorator
+        .append(&" ".repeat(before))
+        .append_text(&content)
+        .reset_face()
+        .append(&" ".repeat(after));
+}
+
+/// Truncates `text` to `width` columns, ending with an ellipsis in the face of the last kept
+/// character. Returns the truncated text with its width.
+pub(crate) fn truncated<F>(text: &Text<F>, width: usize) -> (Text<F>, usize)
+where
+    F: Clone + Default + PartialEq,
+{
+    let mut decorator = Decorator::new();
+    if width == 0 {
+        return (decorator.build(), 0);
+    }
+    let mut used = 0;
+    let mut last_face = None;
+    'fragments: for tf in text {
+        decorator.set_face(tf.face.clone());
+        for (idx, c) in tf.text.char_indices() {
+            let w = c.width().unwrap_or(0);
+            if used + w > width - 1 {
+                decorator.append(&tf.text[..idx]);
+                if idx > 0 || last_face.is_none() {
+                    last_face = Some(tf.face.clone());
+                }
+                break 'fragments;
+            }
+            used += w;
+        }
+        decorator.append(&tf.text);
+        last_face = Some(tf.face.clone());
+    }
+    decorator
+        .set_face(last_face.unwrap_or_default())
+        .append("…");
+    (decorator.build(), used + 1)
+}
@@ -0,0 +1,42 @@
+use crate::Text;
+use ::termwiz::cell::CellAttributes;
+use ::termwiz::surface::Change;
+
+impl<F> Text<F> {
+    /// Converts the decorated text into a sequence of `termwiz` changes, ready to be added to a
+    /// `Surface` or sent to a `Terminal`. Each fragment is preceded by the cell attributes returned
+    /// by `attributes` for its face and the attributes are reset to their defaults at the end.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    /// use termwiz::cell::{CellAttributes, Intensity};
+    /// use termwiz::surface::{Change, Surface};
+    ///
+    /// let text = Decorator::with_text("An important word.").set(true, 3..12).build();
+    /// let changes = text.to_termwiz_changes(|&strong| {
+    ///     let mut attrs = CellAttributes::default();
+    ///     if strong {
+    ///         attrs.set_intensity(Intensity::Bold);
+    ///     }
+    ///     attrs
+    /// });
+    /// assert_eq!(changes.len(), 7);
+    /// assert_eq!(changes[3], Change::Text("important".to_owned()));
+    ///
+    /// let mut surface = Surface::new(20, 1);
+    /// surface.add_changes(changes);
+    /// assert_eq!(surface.screen_chars_to_string().trim_end(), "An important word.");
+    /// ```
+    pub fn to_termwiz_changes<A>(&self, attributes: A) -> Vec<Change>
+    where
+        A: Fn(&F) -> CellAttributes,
+    {
+        let mut changes = Vec::new();
+        for tf in self {
+            changes.push(Change::AllAttributes(attributes(&tf.face)));
+            changes.push(Change::Text(tf.text.clone()));
+        }
+        changes.push(Change::AllAttributes(CellAttributes::default()));
+        changes
+    }
+}
@@ -0,0 +1,122 @@
+//! Test helpers rendering readable differences between strings or texts.
+//!
+//! The [`assert_text_eq!`] macro asserts that two values are equal and, when they are not,
+//! prints a colored line diff of them. The module is available with the `testing` feature,
+//! usually enabled for the dev-dependency only.
+//!
+//! [`assert_text_eq!`]: ../macro.assert_text_eq.html
+
+use crate::diff::{diff_slices, DiffFace};
+use crate::{Decorator, Text};
+use std::fmt::Debug;
+
+/// A value which can be compared line by line.
+pub trait Diffable {
+    /// Returns the representation of the value used to compute the diff.
+    fn diff_repr(&self) -> String;
+}
+
+impl Diffable for str {
+    fn diff_repr(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl Diffable for String {
+    fn diff_repr(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: Diffable + ?Sized> Diffable for &T {
+    fn diff_repr(&self) -> String {
+        (**self).diff_repr()
+    }
+}
+
+/// A text is represented with a line per fragment.
+impl<F: Debug> Diffable for Text<F> {
+    fn diff_repr(&self) -> String {
+        self.iter()
+            .map(|tf| format!("{:?} {:?}\n", tf.face, tf.text))
+            .collect()
+    }
+}
+
+/// Returns the line diff of `old` and `new`. Each line is prefixed by `- `, `+ ` or two spaces
+/// whether it is removed, added or present in both.
+///
+/// ```
+/// use ornament::diff::DiffFace;
+/// use ornament::testing::diff_lines;
+///
+/// let diff = diff_lines("a\nb\nc\n", "a\nc\nd\n");
+/// assert_eq!(diff.plain(), "  a\n- b\n  c\n+ d\n");
+/// assert_eq!(diff.face_at(4), Some(&DiffFace::Removed));
+/// ```
+pub fn diff_lines(old: &str, new: &str) -> Text<DiffFace> {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let mut decorator = Decorator::new();
+    for (face, line) in diff_slices(&old, &new) {
+        let prefix = match face {
+            DiffFace::Equal => "  ",
+            DiffFace::Added => "+ ",
+            DiffFace::Removed => "- ",
+        };
+        decorator.set_face(face).append(prefix).append(line);
+        if !line.ends_with('\n') {
+            decorator.append("\n");
+        }
+    }
+    decorator.build()
+}
+
+/// Renders a diff with ANSI colors: red for removed lines, green for added ones.
+pub fn render_ansi(diff: &Text<DiffFace>) -> String {
+    diff.render(|tf| match tf.face {
+        DiffFace::Equal => tf.text.clone(),
+        DiffFace::Added => format!("\x1b[32m{}\x1b[0m", tf.text),
+        DiffFace::Removed => format!("\x1b[31m{}\x1b[0m", tf.text),
+    })
+}
+
+#[doc(hidden)]
+pub fn diff_report<L, R>(left: &L, right: &R) -> String
+where
+    L: Diffable + ?Sized,
+    R: Diffable + ?Sized,
+{
+    render_ansi(&diff_lines(&left.diff_repr(), &right.diff_repr()))
+}
+
+/// Asserts that two values are equal, printing a colored line diff of them if they are not.
+///
+/// Both values must be [`Diffable`]: strings are compared line by line and texts fragment by
+/// fragment. The diff shows the changes from the left value to the right one.
+///
+/// ```
+/// use ornament::{assert_text_eq, Decorator};
+///
+/// let text = Decorator::with_text("one\ntwo").set(1u8, 4..7).build();
+/// assert_text_eq!(text.plain(), "one\ntwo");
+/// assert_text_eq!(text, Decorator::with_text("one\n").set_face(1).append("two").build());
+/// ```
+///
+/// [`Diffable`]: testing/trait.Diffable.html
+#[macro_export]
+macro_rules! assert_text_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    panic!(
+                        "assertion failed: `(left == right)`\n\n{}",
+                        $crate::testing::diff_report(left, right)
+                    );
+                }
+            }
+        }
+    };
+}
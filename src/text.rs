@@ -1,3 +1,10 @@
+use crate::{FaceWrap, MemoryUsage};
+use std::cmp::min;
+use std::fmt;
+use std::ops::{Deref, DerefMut, Range};
+#[cfg(feature = "unicode-width")]
+use unicode_width::UnicodeWidthStr;
+
 /// A piece of a decorated text.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
@@ -26,17 +33,280 @@ impl<F: Default> From<String> for TextFragment<F> {
     }
 }
 
+/// What [`Text::filter_map_face`] does with the fragments whose face is not mapped.
+///
+/// [`Text::filter_map_face`]: struct.Text.html#method.filter_map_face
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Unmapped {
+    /// The fragment is kept with the default face.
+    #[default]
+    Default,
+    /// The fragment is removed, along with its text.
+    Drop,
+}
+
+/// What [`Text::try_render`] does with a fragment which fails to render.
+///
+/// [`Text::try_render`]: struct.Text.html#method.try_render
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Recovery {
+    /// The fragment is left out of the output.
+    Skip,
+    /// The fragment text is output without decoration.
+    #[default]
+    Plain,
+    /// The given placeholder is output instead of the fragment.
+    Placeholder(String),
+    /// The rendering stops, the output contains the fragments rendered before.
+    Abort,
+}
+
 /// A decorated text. This is a collection of [`TextFragment`].
 ///
+/// The end offset of each fragment is indexed so that position queries ([`locate`], [`face_at`],
+/// [`slice`]) don't have to walk through all the fragments.
+///
 /// [`TextFragment`]: struct.TextFragment.html
-#[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
-pub struct Text<F>(Vec<TextFragment<F>>);
+/// [`locate`]: struct.Text.html#method.locate
+/// [`face_at`]: struct.Text.html#method.face_at
+/// [`slice`]: struct.Text.html#method.slice
+#[derive(Clone, PartialEq)]
+pub struct Text<F> {
+    fragments: Vec<TextFragment<F>>,
+    ends: Vec<usize>,
+}
 
 impl<F> Text<F> {
+    fn index(fragments: &[TextFragment<F>]) -> Vec<usize> {
+        let mut acc = 0;
+        fragments
+            .iter()
+            .map(|tf| {
+                acc += tf.text.len();
+                acc
+            })
+            .collect()
+    }
+
+    /// Returns the index of the fragment containing the byte at `offset` and the position of this
+    /// byte in the fragment text, or `None` if `offset` is out of bounds.
+    pub fn locate(&self, offset: usize) -> Option<(usize, usize)> {
+        let idx = self.ends.partition_point(|&end| end <= offset);
+        if idx == self.ends.len() {
+            return None;
+        }
+        let start = self.ends[idx] - self.fragments[idx].text.len();
+        Some((idx, offset - start))
+    }
+
+    /// Returns `true` if `offset` is the start or the end of a `char` of the text, as
+    /// `str::is_char_boundary` does.
+    pub(crate) fn is_char_boundary(&self, offset: usize) -> bool {
+        match self.locate(offset) {
+            Some((idx, pos)) => self.fragments[idx].text.is_char_boundary(pos),
+            None => offset == self.text_len(),
+        }
+    }
+
+    /// Returns the face assigned to the byte at `offset`, or `None` if `offset` is out of bounds.
+    pub fn face_at(&self, offset: usize) -> Option<&F> {
+        self.locate(offset)
+            .map(|(idx, _)| &self.fragments[idx].face)
+    }
+
+    /// Consumes the text and returns it with each face transformed by `f`.
+    pub fn map_face<G, M>(self, mut f: M) -> Text<G>
+    where
+        M: FnMut(F) -> G,
+    {
+        Text {
+            fragments: self
+                .fragments
+                .into_iter()
+                .map(|tf| TextFragment {
+                    text: tf.text,
+                    face: f(tf.face),
+                })
+                .collect(),
+            ends: self.ends,
+        }
+    }
+
+    /// Returns the text with each face transformed by `f`, the fragments whose face is mapped to
+    /// `None` being handled according to `unmapped`. Contiguous fragments which end up with the
+    /// same face are merged.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Unmapped};
+    ///
+    /// let text = Decorator::with_text("bold red blink")
+    ///     .set("bold", 0..4)
+    ///     .set("red", 5..8)
+    ///     .set("blink", 9..14)
+    ///     .build();
+    /// let markdown = |face: &&str| match *face {
+    ///     "bold" => Some("strong"),
+    ///     "blink" => None,
+    ///     _ => Some(""),
+    /// };
+    /// let converted = text.filter_map_face(Unmapped::Default, markdown);
+    /// assert_eq!(converted.iter().count(), 2);
+    /// assert_eq!(converted.face_at(9), Some(&""));
+    /// let converted = text.filter_map_face(Unmapped::Drop, markdown);
+    /// assert_eq!(converted.plain(), "bold red ");
+    /// ```
+    pub fn filter_map_face<G, M>(&self, unmapped: Unmapped, f: M) -> Text<G>
+    where
+        G: Default + PartialEq,
+        M: Fn(&F) -> Option<G>,
+    {
+        let mut fragments: Vec<TextFragment<G>> = Vec::with_capacity(self.fragments.len());
+        for tf in &self.fragments {
+            let face = match (f(&tf.face), unmapped) {
+                (Some(face), _) => face,
+                (None, Unmapped::Default) => G::default(),
+                (None, Unmapped::Drop) => continue,
+            };
+            match fragments.last_mut() {
+                Some(last) if last.face == face => last.text.push_str(&tf.text),
+                _ => fragments.push(TextFragment {
+                    text: tf.text.clone(),
+                    face,
+                }),
+            }
+        }
+        fragments.into()
+    }
+
+    /// Consumes the text and returns it with each face converted using its `From` implementation.
+    ///
+    /// A blanket `From<Text<F>> for Text<G>` implementation would conflict with the reflexive
+    /// `From<T> for T` one, this method fills this role.
+    ///
+    /// ```
+    /// use ornament::Text;
+    ///
+    /// let text: Text<u8> = Text::from("number");
+    /// let converted: Text<u32> = text.convert();
+    /// assert_eq!(converted.face_at(0), Some(&0u32));
+    /// ```
+    pub fn convert<G: From<F>>(self) -> Text<G> {
+        self.map_face(G::from)
+    }
+
+    /// Consumes the text and returns the text and the face of each fragment.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("a bold word").set("bold", 2..6).build();
+    /// assert_eq!(
+    ///     text.into_pairs(),
+    ///     vec![
+    ///         ("a ".to_owned(), ""),
+    ///         ("bold".to_owned(), "bold"),
+    ///         (" word".to_owned(), ""),
+    ///     ]
+    /// );
+    /// ```
+    pub fn into_pairs(self) -> Vec<(String, F)> {
+        self.fragments
+            .into_iter()
+            .map(|tf| (tf.text, tf.face))
+            .collect()
+    }
+
+    /// Creates a text with a fragment per pair of text and face, the reverse of [`into_pairs`].
+    ///
+    /// ```
+    /// use ornament::Text;
+    ///
+    /// let text = Text::from_pairs(vec![("a ", 0), ("bold", 1), (" word", 0)]);
+    /// assert_eq!(text.face_at(2), Some(&1));
+    /// assert_eq!(text.into_pairs()[1], ("bold".to_owned(), 1));
+    /// ```
+    ///
+    /// [`into_pairs`]: struct.Text.html#method.into_pairs
+    pub fn from_pairs<I, S>(pairs: I) -> Text<F>
+    where
+        I: IntoIterator<Item = (S, F)>,
+        S: Into<String>,
+    {
+        pairs
+            .into_iter()
+            .map(|(text, face)| TextFragment {
+                text: text.into(),
+                face,
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Returns the first fragment, or `None` if the text is empty.
+    pub fn first(&self) -> Option<&TextFragment<F>> {
+        self.fragments.first()
+    }
+
+    /// Returns the last fragment, or `None` if the text is empty.
+    pub fn last(&self) -> Option<&TextFragment<F>> {
+        self.fragments.last()
+    }
+
+    /// Returns a mutable reference to the last fragment, or `None` if the text is empty.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut text = Decorator::with_text("Done.").set("dim", 4..5).build();
+    /// if let Some(mut last) = text.last_mut() {
+    ///     last.text.push('.');
+    /// }
+    /// assert_eq!(text.text_len(), 6);
+    /// assert_eq!(text.face_at(5), Some(&"dim"));
+    /// ```
+    pub fn last_mut(&mut self) -> Option<LastFragmentMut<'_, F>> {
+        if self.fragments.is_empty() {
+            None
+        } else {
+            Some(LastFragmentMut { text: self })
+        }
+    }
+
+    /// Removes the last fragment and returns it, or `None` if the text is empty.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut text = Decorator::with_text("ok\n").set("eol", 2..3).build();
+    /// assert_eq!(text.pop().map(|tf| tf.text), Some("\n".to_owned()));
+    /// assert_eq!(text.plain(), "ok");
+    /// ```
+    pub fn pop(&mut self) -> Option<TextFragment<F>> {
+        self.ends.pop();
+        self.fragments.pop()
+    }
+
     /// Returns the length of the underlying text, without decorations, in bytes.
+    ///
+    /// The length is stored along the fragments, this method does not iterate over them.
     pub fn text_len(&self) -> usize {
-        self.iter().fold(0, |acc, x| acc + x.text.len())
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns the display width of the underlying text, in terminal columns. This method
+    /// requires the `unicode-width` feature.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("日本 ok").set(1, 0..6).build();
+    /// assert_eq!(text.width(), 7);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub fn width(&self) -> usize {
+        self.fragments.iter().map(|tf| tf.text.width()).sum()
     }
 
     /// Converts the decorated text into rich text, using `decorator` to handle the different faces.
@@ -47,9 +317,521 @@ impl<F> Text<F> {
         self.iter().map(decorator).collect::<Vec<String>>().join("")
     }
 
+    /// Converts the decorated text into rich text, wrapping each fragment between the prefix and
+    /// the suffix of its face. This is a shortcut for [`render`] with a decorator doing so.
+    ///
+    /// [`render`]: struct.Text.html#method.render
+    pub fn render_wrapped(&self) -> String
+    where
+        F: FaceWrap,
+    {
+        self.iter().fold(String::new(), |mut acc, tf| {
+            acc.push_str(tf.face.prefix());
+            acc.push_str(&tf.text);
+            acc.push_str(tf.face.suffix());
+            acc
+        })
+    }
+
+    /// Converts the decorated text into rich text as [`render`] does, with a `decorator` which
+    /// can fail. Fragments failing to render are handled according to `recovery` and the errors
+    /// are returned along the output.
+    ///
+    /// ```
+    /// use ornament::{Decorator, Recovery};
+    ///
+    /// let text = Decorator::with_text("ok, unknown and ok")
+    ///     .set("b", 0..2)
+    ///     .set("blink", 4..11)
+    ///     .set("b", 16..18)
+    ///     .build();
+    /// let html = |tf: &ornament::TextFragment<&str>| match tf.face {
+    ///     "" => Ok(tf.text.clone()),
+    ///     "b" => Ok(format!("<b>{}</b>", tf.text)),
+    ///     face => Err(format!("no mapping for {}", face)),
+    /// };
+    /// let (output, errors) = text.try_render(html, Recovery::Plain);
+    /// assert_eq!(output, "<b>ok</b>, unknown and <b>ok</b>");
+    /// assert_eq!(errors, vec!["no mapping for blink"]);
+    ///
+    /// let (output, _) = text.try_render(html, Recovery::Abort);
+    /// assert_eq!(output, "<b>ok</b>, ");
+    /// ```
+    ///
+    /// [`render`]: struct.Text.html#method.render
+    pub fn try_render<G, E>(&self, decorator: G, recovery: Recovery) -> (String, Vec<E>)
+    where
+        G: Fn(&TextFragment<F>) -> Result<String, E>,
+    {
+        let mut output = String::new();
+        let mut errors = Vec::new();
+        for tf in self {
+            match decorator(tf) {
+                Ok(rendered) => output.push_str(&rendered),
+                Err(err) => {
+                    errors.push(err);
+                    match &recovery {
+                        Recovery::Skip => {}
+                        Recovery::Plain => output.push_str(&tf.text),
+                        Recovery::Placeholder(placeholder) => output.push_str(placeholder),
+                        Recovery::Abort => break,
+                    }
+                }
+            }
+        }
+        (output, errors)
+    }
+
+    /// Same as [`render`] but fragments are rendered in parallel, the results are then
+    /// concatenated in order. `decorator` must not rely on the rendering order of the fragments.
+    ///
+    /// [`render`]: struct.Text.html#method.render
+    #[cfg(feature = "rayon")]
+    pub fn render_par<G>(&self, decorator: G) -> String
+    where
+        F: Sync,
+        G: Fn(&TextFragment<F>) -> String + Sync + Send,
+    {
+        use rayon::prelude::*;
+        self.fragments
+            .par_iter()
+            .map(decorator)
+            .collect::<Vec<String>>()
+            .concat()
+    }
+
     /// Converts the decorated text into plain text, stripping all decorations.
     pub fn plain(&self) -> String {
-        self.iter().fold(String::new(), |acc, x| acc + &x.text)
+        self.iter()
+            .fold(String::with_capacity(self.text_len()), |acc, x| {
+                acc + &x.text
+            })
+    }
+
+    /// Returns a report of the memory used by the text and its fragments.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let fragments_bytes = self.fragments.capacity() * std::mem::size_of::<TextFragment<F>>()
+            + self.ends.capacity() * std::mem::size_of::<usize>();
+        let text_bytes = self.text_len();
+        let unused = self
+            .iter()
+            .fold(0, |acc, x| acc + x.text.capacity() - x.text.len());
+        MemoryUsage {
+            fragments: self.fragments.len(),
+            text_bytes,
+            overhead: fragments_bytes + unused,
+        }
+    }
+
+    /// Shrinks the capacity of the fragments storage and of each fragment text as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.fragments.shrink_to_fit();
+        self.ends.shrink_to_fit();
+        for tf in &mut self.fragments {
+            tf.text.shrink_to_fit();
+        }
+    }
+}
+
+impl<F: Clone> Text<F> {
+    /// Returns the decorated text in the given byte range. The range is clamped to the length of
+    /// the text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a bound of the range is not on a `char` boundary.
+    pub fn slice(&self, range: Range<usize>) -> Text<F> {
+        let end = min(range.end, self.text_len());
+        if range.start >= end {
+            return Text::default();
+        }
+        let (first, first_offset) = self.locate(range.start).expect("invalid offset");
+        let (last, last_offset) = self.locate(end - 1).expect("invalid offset");
+        let mut fragments = self.fragments[first..=last].to_vec();
+        if let Some(tf) = fragments.last_mut() {
+            tf.text.truncate(last_offset + 1);
+        }
+        if let Some(tf) = fragments.first_mut() {
+            tf.text.replace_range(..first_offset, "");
+        }
+        fragments.into()
+    }
+
+    /// Returns the lines of the decorated text. As with `str::lines`, lines are split on `\n` or
+    /// `\r\n`, which are not included, and a final line ending doesn't produce an empty line.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("one\r\ntwo\n").set(1, 2..6).build();
+    /// let lines = text.lines();
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0].plain(), "one");
+    /// assert_eq!(lines[1].face_at(0), Some(&1));
+    /// ```
+    pub fn lines(&self) -> Vec<Text<F>> {
+        let plain = self.plain();
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in plain.split_inclusive('\n') {
+            let content = match line.strip_suffix('\n') {
+                Some(content) => content.strip_suffix('\r').unwrap_or(content),
+                None => line,
+            };
+            lines.push(self.slice(start..start + content.len()));
+            start += line.len();
+        }
+        lines
+    }
+
+    /// Returns the lines of the decorated text, including their line ending, so that
+    /// concatenating them gives back the text. As with `str::split_inclusive`, a final line
+    /// ending doesn't produce an empty line.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("one\r\ntwo\n").set(1, 2..6).build();
+    /// let lines = text.lines_inclusive();
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0].plain(), "one\r\n");
+    /// assert_eq!(lines[0].face_at(4), Some(&1));
+    /// assert_eq!(lines[1].plain(), "two\n");
+    /// ```
+    pub fn lines_inclusive(&self) -> Vec<Text<F>> {
+        let plain = self.plain();
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in plain.split_inclusive('\n') {
+            lines.push(self.slice(start..start + line.len()));
+            start += line.len();
+        }
+        lines
+    }
+
+    /// Splits the decorated text on `sep`, returning at most `n` pieces, the last one containing
+    /// the remainder of the text, as `str::splitn` does.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("url: http://a.org").set(1, 5..17).build();
+    /// let pieces = text.splitn(2, ":");
+    /// assert_eq!(pieces.len(), 2);
+    /// assert_eq!(pieces[0].plain(), "url");
+    /// assert_eq!(pieces[1].plain(), " http://a.org");
+    /// assert_eq!(pieces[1].face_at(1), Some(&1));
+    /// ```
+    pub fn splitn(&self, n: usize, sep: &str) -> Vec<Text<F>> {
+        let plain = self.plain();
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        for piece in plain.splitn(n, sep) {
+            pieces.push(self.slice(start..start + piece.len()));
+            start += piece.len() + sep.len();
+        }
+        pieces
+    }
+
+    /// Splits the decorated text after each character matching `pred`, keeping it at the end of
+    /// its piece, as `str::split_inclusive` does.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("Hi. Ok? Yes").set(1, 4..7).build();
+    /// let sentences = text.split_inclusive(|c| c == '.' || c == '?');
+    /// assert_eq!(
+    ///     sentences.iter().map(|s| s.plain()).collect::<Vec<_>>(),
+    ///     vec!["Hi.", " Ok?", " Yes"]
+    /// );
+    /// assert_eq!(sentences[1].face_at(3), Some(&1));
+    /// ```
+    pub fn split_inclusive<P>(&self, pred: P) -> Vec<Text<F>>
+    where
+        P: Fn(char) -> bool,
+    {
+        let plain = self.plain();
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        for piece in plain.split_inclusive(pred) {
+            pieces.push(self.slice(start..start + piece.len()));
+            start += piece.len();
+        }
+        pieces
+    }
+
+    /// Splits the decorated text into pages of `lines_per_page` lines. Pages keep their line
+    /// breaks, so that concatenating them gives back the text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lines_per_page` is zero.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("1\n2\n3\n4\n5").set(1, 2..5).build();
+    /// let pages = text.paginate(2);
+    /// assert_eq!(
+    ///     pages.iter().map(|page| page.plain()).collect::<Vec<_>>(),
+    ///     vec!["1\n2\n", "3\n4\n", "5"]
+    /// );
+    /// assert_eq!(pages[1].face_at(0), Some(&1));
+    /// ```
+    pub fn paginate(&self, lines_per_page: usize) -> Vec<Text<F>> {
+        assert!(lines_per_page > 0, "pages must have at least one line");
+        let plain = self.plain();
+        let mut pages = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+        for (idx, line) in plain.split_inclusive('\n').enumerate() {
+            end += line.len();
+            if (idx + 1) % lines_per_page == 0 {
+                pages.push(self.slice(start..end));
+                start = end;
+            }
+        }
+        if start < end {
+            pages.push(self.slice(start..end));
+        }
+        pages
+    }
+}
+
+impl<F: Default + PartialEq> Text<F> {
+    /// Replaces `face` by the default face across the text, such as transient search or selection
+    /// highlights, merging the fragments which end up with the same face.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut text = Decorator::with_text("find me").set("match", 5..7).build();
+    /// text.strip_face(&"match");
+    /// assert_eq!(text.iter().count(), 1);
+    /// assert_eq!(text.face_at(5), Some(&""));
+    /// ```
+    pub fn strip_face(&mut self, face: &F) {
+        for tf in &mut self.fragments {
+            if tf.face == *face {
+                tf.face = F::default();
+            }
+        }
+        self.merge_fragments();
+    }
+}
+
+impl<F: PartialEq> Text<F> {
+    /// Replaces `old` by `new` across the text, merging the fragments which end up with the same
+    /// face. Unlike [`map_face`], the text is modified in place.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let mut text = Decorator::with_text("warning: unused").set("warning", 0..7).build();
+    /// text.replace_face(&"warning", "info");
+    /// assert_eq!(text.face_at(0), Some(&"info"));
+    /// ```
+    ///
+    /// [`map_face`]: struct.Text.html#method.map_face
+    pub fn replace_face(&mut self, old: &F, new: F)
+    where
+        F: Clone,
+    {
+        for tf in &mut self.fragments {
+            if tf.face == *old {
+                tf.face = new.clone();
+            }
+        }
+        self.merge_fragments();
+    }
+
+    /// Returns the ranges decorated with `face`, with the decorated text they cover. The
+    /// contiguous fragments with this face form a single range.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("see src/lib.rs and Cargo.toml")
+    ///     .set("path", 4..14)
+    ///     .set("path", 19..29)
+    ///     .build();
+    /// let paths = text.select(&"path");
+    /// assert_eq!(paths.len(), 2);
+    /// assert_eq!(paths[1].0, 19..29);
+    /// assert_eq!(paths[1].1.plain(), "Cargo.toml");
+    /// ```
+    pub fn select(&self, face: &F) -> Vec<(Range<usize>, Text<F>)>
+    where
+        F: Clone,
+    {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (range, tf) in self.fragments_with_offsets() {
+            if tf.face == *face {
+                match ranges.last_mut() {
+                    Some(last) if last.end == range.start => last.end = range.end,
+                    _ => ranges.push(range),
+                }
+            }
+        }
+        ranges
+            .into_iter()
+            .map(|range| {
+                let text = self.slice(range.clone());
+                (range, text)
+            })
+            .collect()
+    }
+
+    fn merge_fragments(&mut self) {
+        let mut fragments: Vec<TextFragment<F>> = Vec::with_capacity(self.fragments.len());
+        for tf in self.fragments.drain(..) {
+            match fragments.last_mut() {
+                Some(last) if last.face == tf.face => last.text.push_str(&tf.text),
+                _ => fragments.push(tf),
+            }
+        }
+        *self = fragments.into();
+    }
+}
+
+#[cfg(feature = "unicode-width")]
+impl<F: Clone + fmt::Debug + Default + PartialEq> Text<F> {
+    /// Renders the plain text with the placement of its faces, for debugging purposes.
+    ///
+    /// Each line is followed by a row per face used in it, other than the default one, where
+    /// the text with this face is underlined with carets. The row ends with the face, formatted
+    /// with `Debug`. This method requires the `unicode-width` feature.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = y;\nx + 1")
+    ///     .set("var", 4..5)
+    ///     .set("var", 8..9)
+    ///     .set("keyword", 0..3)
+    ///     .set("var", 11..12)
+    ///     .build();
+    /// assert_eq!(
+    ///     text.debug_render(),
+    ///     concat!(
+    ///         "let x = y;\n",
+    ///         "^^^ \"keyword\"\n",
+    ///         "    ^   ^ \"var\"\n",
+    ///         "x + 1\n",
+    ///         "^ \"var\"\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn debug_render(&self) -> String {
+        let default = F::default();
+        let mut out = String::new();
+        for line in self.lines() {
+            let mut rows: Vec<(&F, String)> = Vec::new();
+            let mut column = 0;
+            for tf in &line {
+                let width = tf.text.width();
+                if tf.face != default && width > 0 {
+                    let idx = match rows.iter().position(|(face, _)| **face == tf.face) {
+                        Some(idx) => idx,
+                        None => {
+                            rows.push((&tf.face, String::new()));
+                            rows.len() - 1
+                        }
+                    };
+                    let row = &mut rows[idx].1;
+                    let padding = column - row.len();
+                    row.push_str(&" ".repeat(padding));
+                    row.push_str(&"^".repeat(width));
+                }
+                column += width;
+            }
+            out.push_str(&line.plain());
+            out.push('\n');
+            for (face, row) in rows {
+                out.push_str(&format!("{} {:?}\n", row, face));
+            }
+        }
+        out
+    }
+}
+
+/// A mutable reference to the last fragment of a [`Text`], returned by [`Text::last_mut`].
+///
+/// The offsets indexed by the text are updated when it is dropped.
+///
+/// [`Text`]: struct.Text.html
+/// [`Text::last_mut`]: struct.Text.html#method.last_mut
+pub struct LastFragmentMut<'a, F> {
+    text: &'a mut Text<F>,
+}
+
+impl<F> Deref for LastFragmentMut<'_, F> {
+    type Target = TextFragment<F>;
+
+    fn deref(&self) -> &TextFragment<F> {
+        self.text.fragments.last().expect("empty text")
+    }
+}
+
+impl<F> DerefMut for LastFragmentMut<'_, F> {
+    fn deref_mut(&mut self) -> &mut TextFragment<F> {
+        self.text.fragments.last_mut().expect("empty text")
+    }
+}
+
+impl<F> Drop for LastFragmentMut<'_, F> {
+    fn drop(&mut self) {
+        let Text { fragments, ends } = &mut *self.text;
+        if let (Some(tf), Some(_)) = (fragments.last(), ends.pop()) {
+            let start = ends.last().copied().unwrap_or(0);
+            ends.push(start + tf.text.len());
+        }
+    }
+}
+
+impl<F> Default for Text<F> {
+    fn default() -> Self {
+        Text {
+            fragments: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+}
+
+impl<F: fmt::Debug> fmt::Debug for Text<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Text").field(&self.fragments).finish()
+    }
+}
+
+impl<F> Text<F> {
+    fn eq_plain(&self, s: &str) -> bool {
+        self.text_len() == s.len()
+            && self
+                .fragments_with_offsets()
+                .all(|(range, tf)| s.get(range) == Some(tf.text.as_str()))
+    }
+}
+
+/// Compares the underlying text, without decorations, to a string.
+///
+/// ```
+/// use ornament::Decorator;
+///
+/// let text = Decorator::with_text("file not found").set(1, 0..4).build();
+/// assert_eq!(text, "file not found");
+/// assert_ne!(text, "file");
+/// ```
+impl<F> PartialEq<&str> for Text<F> {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_plain(other)
+    }
+}
+
+impl<F> PartialEq<String> for Text<F> {
+    fn eq(&self, other: &String) -> bool {
+        self.eq_plain(other)
     }
 }
 
@@ -58,7 +840,43 @@ pub type TextIterator<'a, F> = std::slice::Iter<'a, TextFragment<F>>;
 impl<F> Text<F> {
     /// Returns an iterator over the text.
     pub fn iter(&self) -> TextIterator<'_, F> {
-        self.0.iter()
+        self.fragments.iter()
+    }
+
+    /// Returns an iterator over the fragments with the byte range each one covers in the text.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = 1;").set("var", 4..5).build();
+    /// let ranges: Vec<_> = text.fragments_with_offsets().map(|(range, _)| range).collect();
+    /// assert_eq!(ranges, vec![0..4, 4..5, 5..10]);
+    /// ```
+    pub fn fragments_with_offsets(
+        &self,
+    ) -> impl Iterator<Item = (Range<usize>, &TextFragment<F>)> + '_ {
+        self.fragments
+            .iter()
+            .zip(&self.ends)
+            .map(|(tf, &end)| (end - tf.text.len()..end, tf))
+    }
+
+    /// Returns an iterator over the characters of the text with their byte offset and face, as
+    /// `str::char_indices` does.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("aé!").set(1, 1..3).build();
+    /// let chars: Vec<_> = text.char_indices().collect();
+    /// assert_eq!(chars, vec![(0, 'a', &0), (1, 'é', &1), (3, '!', &0)]);
+    /// ```
+    pub fn char_indices(&self) -> impl Iterator<Item = (usize, char, &F)> + '_ {
+        self.fragments_with_offsets().flat_map(|(range, tf)| {
+            tf.text
+                .char_indices()
+                .map(move |(idx, c)| (range.start + idx, c, &tf.face))
+        })
     }
 }
 
@@ -73,30 +891,45 @@ impl<'a, F> IntoIterator for &'a Text<F> {
 
 impl<F> From<Vec<TextFragment<F>>> for Text<F> {
     fn from(tfs: Vec<TextFragment<F>>) -> Text<F> {
-        Text(tfs)
+        Text {
+            ends: Text::index(&tfs),
+            fragments: tfs,
+        }
     }
 }
 
 impl<F> From<TextFragment<F>> for Text<F> {
     fn from(tf: TextFragment<F>) -> Text<F> {
-        Text(vec![tf])
+        vec![tf].into()
     }
 }
 
 impl<F: Default> From<&str> for Text<F> {
     fn from(s: &str) -> Text<F> {
-        Text(vec![TextFragment {
-            text: s.to_owned(),
-            face: F::default(),
-        }])
+        TextFragment::from(s).into()
     }
 }
 
 impl<F: Default> From<String> for Text<F> {
     fn from(s: String) -> Text<F> {
-        Text(vec![TextFragment {
-            text: s.to_owned(),
-            face: F::default(),
-        }])
+        TextFragment::from(s).into()
+    }
+}
+
+#[cfg(feature = "serde_support")]
+impl<F: serde::Serialize> serde::Serialize for Text<F> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct("Text", &self.fragments)
+    }
+}
+
+#[cfg(feature = "serde_support")]
+impl<'de, F: serde::Deserialize<'de>> serde::Deserialize<'de> for Text<F> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Text")]
+        struct Fragments<F>(Vec<TextFragment<F>>);
+
+        Fragments::deserialize(deserializer).map(|Fragments(tfs)| tfs.into())
     }
 }
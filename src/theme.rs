@@ -0,0 +1,169 @@
+use crate::{FaceResolve, Text, TextFragment};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A mapping from faces to concrete styles.
+///
+/// It decouples what a face means (an error, a keyword, a match) from how it looks: texts are
+/// decorated with meaningful faces and rendered with a `Theme` which can be changed without
+/// building the texts again. Faces missing from the theme use its default style.
+///
+/// ```
+/// use ornament::{Decorator, Theme};
+///
+/// #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// enum Face {
+///     #[default]
+///     Default,
+///     Keyword,
+/// }
+///
+/// let text = Decorator::with_text("let x = 1;")
+///     .set(Face::Keyword, 0..3)
+///     .build();
+///
+/// let mut theme = Theme::new("");
+/// theme.insert(Face::Keyword, "**");
+/// let rendered = text.render_themed(&theme, |text, style| format!("{0}{1}{0}", style, text));
+/// assert_eq!(rendered, "**let** x = 1;");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Theme<F, S> {
+    styles: HashMap<F, S>,
+    default: S,
+}
+
+impl<F, S> Theme<F, S>
+where
+    F: Eq + Hash,
+{
+    /// Creates a new empty `Theme`, `default` is used for the faces without a style.
+    pub fn new(default: S) -> Theme<F, S> {
+        Theme {
+            styles: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Assigns `style` to `face`, returning the previous style of `face` if any.
+    pub fn insert(&mut self, face: F, style: S) -> Option<S> {
+        self.styles.insert(face, style)
+    }
+
+    /// Removes the style of `face`, returning it if any.
+    pub fn remove(&mut self, face: &F) -> Option<S> {
+        self.styles.remove(face)
+    }
+
+    /// Returns the style of `face` if it has one.
+    pub fn get(&self, face: &F) -> Option<&S> {
+        self.styles.get(face)
+    }
+
+    /// Returns the default style.
+    pub fn default_style(&self) -> &S {
+        &self.default
+    }
+
+    /// Returns the style of `face`, or the default style if it has none.
+    pub fn resolve(&self, face: &F) -> &S {
+        self.get(face).unwrap_or(&self.default)
+    }
+
+    /// Returns the style of `face`, falling back to the style of its nearest ancestor, or to the
+    /// default style if none of them has one.
+    pub fn resolve_inherited(&self, face: &F) -> &S
+    where
+        F: FaceResolve,
+    {
+        if let Some(style) = self.get(face) {
+            return style;
+        }
+        let mut current = face.parent();
+        while let Some(ancestor) = current {
+            if let Some(style) = self.get(&ancestor) {
+                return style;
+            }
+            current = ancestor.parent();
+        }
+        &self.default
+    }
+}
+
+impl<F, S> Default for Theme<F, S>
+where
+    F: Eq + Hash,
+    S: Default,
+{
+    fn default() -> Self {
+        Theme::new(S::default())
+    }
+}
+
+impl<F, S> Extend<(F, S)> for Theme<F, S>
+where
+    F: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = (F, S)>>(&mut self, iter: I) {
+        self.styles.extend(iter)
+    }
+}
+
+impl<F> Text<F>
+where
+    F: Eq + Hash,
+{
+    /// Converts the decorated text into rich text, resolving faces to styles with `theme`.
+    /// `decorator` renders a piece of text with its resolved style.
+    pub fn render_themed<S, G>(&self, theme: &Theme<F, S>, decorator: G) -> String
+    where
+        G: Fn(&str, &S) -> String,
+    {
+        self.render(|tf: &TextFragment<F>| decorator(&tf.text, theme.resolve(&tf.face)))
+    }
+
+    /// Same as [`render_themed`] but faces without a style fall back to the style of their
+    /// ancestors (see [`Theme::resolve_inherited`]).
+    ///
+    /// ```
+    /// use ornament::{FaceResolve, Text, TextFragment, Theme};
+    ///
+    /// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    /// enum Face {
+    ///     Plain,
+    ///     Comment,
+    ///     DocComment,
+    /// }
+    ///
+    /// impl FaceResolve for Face {
+    ///     fn parent(&self) -> Option<Self> {
+    ///         match self {
+    ///             Face::DocComment => Some(Face::Comment),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let text = Text::from(vec![
+    ///     TextFragment { text: "x; ".to_owned(), face: Face::Plain },
+    ///     TextFragment { text: "/// doc".to_owned(), face: Face::DocComment },
+    /// ]);
+    /// let mut theme = Theme::new("");
+    /// theme.insert(Face::Comment, "dim");
+    /// let rendered = text.render_inherited(&theme, |text, style| match *style {
+    ///     "" => text.to_owned(),
+    ///     _ => format!("<{0}>{1}</{0}>", style, text),
+    /// });
+    /// assert_eq!(rendered, "x; <dim>/// doc</dim>");
+    /// ```
+    ///
+    /// [`render_themed`]: struct.Text.html#method.render_themed
+    /// [`Theme::resolve_inherited`]: struct.Theme.html#method.resolve_inherited
+    pub fn render_inherited<S, G>(&self, theme: &Theme<F, S>, decorator: G) -> String
+    where
+        F: FaceResolve,
+        G: Fn(&str, &S) -> String,
+    {
+        self.render(|tf: &TextFragment<F>| decorator(&tf.text, theme.resolve_inherited(&tf.face)))
+    }
+}
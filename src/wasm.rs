@@ -0,0 +1,76 @@
+use crate::Text;
+use wasm_bindgen::JsValue;
+use web_sys::{Document, DocumentFragment};
+
+impl<F> Text<F> {
+    /// Converts the decorated text into a DOM document fragment, ready to be inserted in a page.
+    ///
+    /// Each fragment becomes a `<span>` with the class returned by `class` for its face, or a bare
+    /// text node when `class` returns `None`.
+    ///
+    /// ```no_run
+    /// use ornament::Decorator;
+    ///
+    /// fn highlight(document: &web_sys::Document, parent: &web_sys::Element) {
+    ///     let text = Decorator::with_text("let x = 1;").set("keyword", 0..3).build();
+    ///     let fragment = text
+    ///         .to_dom(document, |face| match *face {
+    ///             "" => None,
+    ///             class => Some(class.to_owned()),
+    ///         })
+    ///         .unwrap();
+    ///     parent.append_child(&fragment).unwrap();
+    /// }
+    /// ```
+    pub fn to_dom<C>(&self, document: &Document, class: C) -> Result<DocumentFragment, JsValue>
+    where
+        C: Fn(&F) -> Option<String>,
+    {
+        let fragment = document.create_document_fragment();
+        for tf in self {
+            match class(&tf.face) {
+                Some(class) => {
+                    let span = document.create_element("span")?;
+                    span.set_class_name(&class);
+                    span.set_text_content(Some(&tf.text));
+                    fragment.append_child(&span)?;
+                }
+                None => {
+                    fragment.append_child(&document.create_text_node(&tf.text))?;
+                }
+            }
+        }
+        Ok(fragment)
+    }
+}
+
+#[cfg(feature = "wasm-serde")]
+impl<F> Text<F> {
+    /// Converts the text into a JS value, with the same shape as its serialized form: an array of
+    /// objects with `text` and `face` properties.
+    ///
+    /// ```no_run
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("let x = 1;").set("keyword", 0..3).build();
+    /// let value = text.to_js_value().unwrap();
+    /// let back = ornament::Text::<String>::from_js_value(value).unwrap();
+    /// assert_eq!(back.plain(), "let x = 1;");
+    /// ```
+    pub fn to_js_value(&self) -> Result<JsValue, serde_wasm_bindgen::Error>
+    where
+        F: serde::Serialize,
+    {
+        serde_wasm_bindgen::to_value(self)
+    }
+
+    /// Creates a text from a JS value produced by [`to_js_value`].
+    ///
+    /// [`to_js_value`]: struct.Text.html#method.to_js_value
+    pub fn from_js_value(value: JsValue) -> Result<Text<F>, serde_wasm_bindgen::Error>
+    where
+        F: serde::de::DeserializeOwned,
+    {
+        serde_wasm_bindgen::from_value(value)
+    }
+}
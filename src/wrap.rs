@@ -0,0 +1,278 @@
+use crate::{Decorator, Text};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Splits long words when wrapping, returning the byte offsets where a word can be broken and
+/// the text to insert at the break with its face.
+type Breaker<'a, F> = Option<(&'a dyn Fn(&str) -> Vec<usize>, &'a str, &'a F)>;
+
+impl<F: Clone + Default + PartialEq> Text<F> {
+    /// Wraps the decorated text into lines of at most `width` columns, breaking lines between
+    /// words. The whitespace at line breaks is removed and words wider than `width` are split.
+    ///
+    /// Existing line breaks are kept, the indentation of a line is kept on its first wrapped
+    /// line.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("a decorated text to wrap").set(1, 2..11).build();
+    /// let lines = text.wrap(10);
+    /// assert_eq!(
+    ///     lines.iter().map(|line| line.plain()).collect::<Vec<_>>(),
+    ///     vec!["a", "decorated", "text to", "wrap"]
+    /// );
+    /// assert_eq!(lines[1].face_at(0), Some(&1));
+    /// ```
+    pub fn wrap(&self, width: usize) -> Vec<Text<F>> {
+        self.wrap_with(width, None)
+    }
+
+    /// Wraps the decorated text as [`wrap`] does, breaking words at the hyphenation points found
+    /// with `dictionary` when they don't fit on a line. A hyphen is inserted in `hyphen_face` at
+    /// each break.
+    ///
+    /// ```
+    /// use hyphenation::{Language, Load, Standard};
+    /// use ornament::Decorator;
+    ///
+    /// let dictionary = Standard::from_embedded(Language::EnglishUS).unwrap();
+    /// let text = Decorator::<u8>::with_text("an extraordinary story").build();
+    /// let lines = text.wrap_hyphenated(12, &dictionary, 1);
+    /// assert_eq!(
+    ///     lines.iter().map(|line| line.plain()).collect::<Vec<_>>(),
+    ///     vec!["an extraor-", "dinary story"]
+    /// );
+    /// assert_eq!(lines[0].face_at(10), Some(&1));
+    /// ```
+    ///
+    /// [`wrap`]: struct.Text.html#method.wrap
+    #[cfg(feature = "hyphenation")]
+    pub fn wrap_hyphenated(
+        &self,
+        width: usize,
+        dictionary: &hyphenation::Standard,
+        hyphen_face: F,
+    ) -> Vec<Text<F>> {
+        use hyphenation::Hyphenator;
+
+        let breaks = |word: &str| {
+            let lowercase = word.to_lowercase();
+            if lowercase.len() == word.len() {
+                dictionary.hyphenate(&lowercase).breaks
+            } else {
+                Vec::new()
+            }
+        };
+        self.wrap_with(width, Some((&breaks, "-", &hyphen_face)))
+    }
+
+    /// Wraps the decorated text as [`wrap`] does and justifies the lines: spaces are added
+    /// between words so that each line is `width` columns wide.
+    ///
+    /// The last line of each paragraph (before a line break or the end of the text) and lines
+    /// made of a single word are left as is. Added spaces take the face of the space they extend.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("a b cd ef\ngh").set(1, 0..1).build();
+    /// let lines = text.justify(8);
+    /// assert_eq!(
+    ///     lines.iter().map(|line| line.plain()).collect::<Vec<_>>(),
+    ///     vec!["a  b  cd", "ef", "gh"]
+    /// );
+    /// ```
+    ///
+    /// [`wrap`]: struct.Text.html#method.wrap
+    pub fn justify(&self, width: usize) -> Vec<Text<F>> {
+        let mut lines = Vec::new();
+        for paragraph in self.lines() {
+            let mut wrapped = Vec::new();
+            wrap_line(&paragraph, width, None, &mut wrapped);
+            let last = wrapped.pop();
+            lines.extend(wrapped.iter().map(|line| justify_line(line, width)));
+            lines.extend(last);
+        }
+        lines
+    }
+
+    /// Truncates the decorated text to `width` columns, ending with `ellipsis` in the face of the
+    /// last kept character. The text is cut at the end of the last word that fits, so that words
+    /// are not split, unless the first word doesn't fit.
+    ///
+    /// The text is returned as is if it fits in `width`.
+    ///
+    /// ```
+    /// use ornament::Decorator;
+    ///
+    /// let text = Decorator::with_text("New message from Alice").set(1, 0..11).build();
+    /// let truncated = text.truncate_at_word(16, "…");
+    /// assert_eq!(truncated.plain(), "New message…");
+    /// assert_eq!(truncated.face_at(11), Some(&1));
+    /// assert_eq!(text.truncate_at_word(8, "...").plain(), "New...");
+    /// ```
+    pub fn truncate_at_word(&self, width: usize, ellipsis: &str) -> Text<F> {
+        if self.width() <= width {
+            return self.clone();
+        }
+        let plain = self.plain();
+        let budget = width.saturating_sub(ellipsis.width());
+        let mut fits = 0;
+        let mut word_end = None;
+        let mut used = 0;
+        for (idx, c) in plain.char_indices() {
+            used += c.width().unwrap_or(0);
+            if used > budget {
+                if c.is_whitespace() {
+                    word_end = Some(idx);
+                }
+                break;
+            }
+            if c.is_whitespace() && idx > 0 && !plain[..idx].ends_with(char::is_whitespace) {
+                word_end = Some(idx);
+            }
+            fits = idx + c.len_utf8();
+        }
+        let end = word_end.map_or(fits, |end| plain[..end].trim_end().len());
+
+        let mut decorator = Decorator::from_text(self.slice(0..end));
+        let face = match end {
+            0 => self.face_at(0),
+            _ => self.face_at(
+                plain[..end]
+                    .char_indices()
+                    .next_back()
+                    .map_or(0, |(idx, _)| idx),
+            ),
+        };
+        decorator
+            .set_face(face.cloned().unwrap_or_default())
+            .append(ellipsis);
+        decorator.build()
+    }
+
+    fn wrap_with(&self, width: usize, breaker: Breaker<'_, F>) -> Vec<Text<F>> {
+        let mut lines = Vec::new();
+        for line in self.lines() {
+            wrap_line(&line, width, breaker, &mut lines);
+        }
+        lines
+    }
+}
+
+fn wrap_line<F>(line: &Text<F>, width: usize, breaker: Breaker<'_, F>, lines: &mut Vec<Text<F>>)
+where
+    F: Clone + Default + PartialEq,
+{
+    let plain = line.plain();
+    let mut words = Vec::new();
+    let mut word_start = None;
+    for (idx, c) in plain.char_indices() {
+        match (c.is_whitespace(), word_start) {
+            (false, None) => word_start = Some(idx),
+            (true, Some(start)) => {
+                words.push(start..idx);
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(start..plain.len());
+    }
+
+    // The current line spans `begin..end` and is `used` columns wide.
+    let (mut begin, mut end, mut used) = (0, 0, 0);
+    let mut has_content = false;
+    for word in words {
+        let breaks = breaker.map_or_else(Vec::new, |(breaks, ..)| {
+            breaks(&plain[word.clone()])
+                .into_iter()
+                .map(|b| word.start + b)
+                .collect()
+        });
+        let mut start = word.start;
+        while start < word.end {
+            let gap = plain[end..start].width();
+            if used + gap + plain[start..word.end].width() <= width {
+                end = word.end;
+                used += gap + plain[start..word.end].width();
+                has_content = true;
+                break;
+            }
+
+            let hyphen_break = breaker.and_then(|(_, hyphen, _)| {
+                breaks.iter().copied().rev().find(|&b| {
+                    start < b
+                        && b < word.end
+                        && used + gap + plain[start..b].width() + hyphen.width() <= width
+                })
+            });
+            if let (Some(b), Some((_, hyphen, face))) = (hyphen_break, breaker) {
+                let mut decorator = Decorator::from_text(line.slice(begin..b));
+                lines.push(decorator.set_face(face.clone()).append(hyphen).build());
+            } else if has_content {
+                lines.push(line.slice(begin..end));
+                begin = start;
+                end = start;
+                used = 0;
+                has_content = false;
+                continue;
+            } else {
+                let mut cut = start;
+                let mut cut_width = used + gap;
+                for (idx, c) in plain[start..word.end].char_indices() {
+                    let w = c.width().unwrap_or(0);
+                    if idx > 0 && cut_width + w > width {
+                        break;
+                    }
+                    cut = start + idx + c.len_utf8();
+                    cut_width += w;
+                }
+                lines.push(line.slice(begin..cut));
+                start = cut;
+            }
+            start = hyphen_break.unwrap_or(start);
+            begin = start;
+            end = start;
+            used = 0;
+            has_content = false;
+        }
+    }
+    if has_content || begin == 0 {
+        lines.push(line.slice(begin..end));
+    }
+}
+
+fn justify_line<F>(line: &Text<F>, width: usize) -> Text<F>
+where
+    F: Clone + Default + PartialEq,
+{
+    let plain = line.plain();
+    let content_start = plain.len() - plain.trim_start().len();
+    let mut gaps = Vec::new();
+    let mut in_gap = false;
+    for (idx, c) in plain[content_start..].char_indices() {
+        if c.is_whitespace() && !in_gap {
+            gaps.push(content_start + idx);
+        }
+        in_gap = c.is_whitespace();
+    }
+    let missing = width.saturating_sub(line.width());
+    if gaps.is_empty() || missing == 0 {
+        return line.clone();
+    }
+
+    let mut decorator = Decorator::new();
+    let mut offset = 0;
+    for (idx, &gap) in gaps.iter().enumerate() {
+        decorator.append_text(&line.slice(offset..gap));
+        let extra = missing / gaps.len() + usize::from(idx < missing % gaps.len());
+        decorator
+            .set_face(line.face_at(gap).cloned().unwrap_or_default())
+            .append(&" ".repeat(extra));
+        offset = gap;
+    }
+    decorator.append_text(&line.slice(offset..plain.len()));
+    decorator.build()
+}